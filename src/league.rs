@@ -7,6 +7,7 @@ use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
 
+pub mod matchup;
 pub mod roster;
 pub mod scoring;
 
@@ -56,9 +57,18 @@ pub fn add_new_league(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
     }
 
     let roster = roster::add(league_dir);
-    match roster {
+    let roster = match roster {
         Ok(r) => {
             info!("Saved the roster: {:#?}", r);
+            r
+        }
+        Err(e) => return Err(e),
+    };
+
+    let schedule = matchup::add(league_dir, &roster);
+    match schedule {
+        Ok(s) => {
+            info!("Saved the matchup schedule: {:#?}", s);
             Ok(())
         }
         Err(e) => Err(e),
@@ -0,0 +1,291 @@
+use crate::league::roster::{self, Roster};
+use crate::utils;
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct InvalidPlayoffFormat(pub usize, pub usize);
+impl fmt::Display for InvalidPlayoffFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "playoff format with {} teams and {} byes cannot be played as a single elimination bracket",
+            self.0, self.1
+        )
+    }
+}
+impl Error for InvalidPlayoffFormat {}
+
+#[derive(Debug, Clone)]
+pub struct TooManyPlayoffTeams(pub usize, pub usize);
+impl fmt::Display for TooManyPlayoffTeams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "playoff format with {} teams cannot be played by a league of {} teams",
+            self.0, self.1
+        )
+    }
+}
+impl Error for TooManyPlayoffTeams {}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Matchup {
+    pub home: String,
+    pub away: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Week {
+    pub start: String,
+    pub end: String,
+    pub matchups: Vec<Matchup>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct PlayoffFormat {
+    pub teams: usize,
+    pub byes: usize,
+}
+impl PlayoffFormat {
+    /// Returns the number of playoff rounds. The top `byes` seeds skip the first round,
+    /// and the remaining teams are reseeded (highest vs lowest) every round.
+    pub fn rounds(&self) -> Result<usize, InvalidPlayoffFormat> {
+        let invalid = InvalidPlayoffFormat(self.teams, self.byes);
+        if self.teams < 2 || self.byes >= self.teams || self.teams % 2 != self.byes % 2 {
+            return Err(invalid);
+        }
+
+        let after_first_round = self.byes + (self.teams - self.byes) / 2;
+        if !after_first_round.is_power_of_two() {
+            return Err(invalid);
+        }
+
+        Ok(1 + after_first_round.trailing_zeros() as usize)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Schedule {
+    pub weeks: Vec<Week>,
+    pub playoff: PlayoffFormat,
+}
+impl Schedule {
+    pub fn week_of(&self, date: &str) -> Option<&Week> {
+        self.weeks
+            .iter()
+            .find(|w| w.start.as_str() <= date && date <= w.end.as_str())
+    }
+}
+
+pub fn add(dir: &str, roster: &Roster) -> Result<Schedule, Box<dyn Error>> {
+    let filepath = &format!("{}/schedule.json", dir);
+
+    let num_teams = team_names(roster).len();
+    let playoff = loop {
+        let teams = roster::get_usize_stdin("How many teams make the playoffs?", 2, num_teams)?;
+        let byes =
+            roster::get_usize_stdin("How many top seeds get a first round bye?", 0, teams - 1)?;
+        let format = PlayoffFormat { teams, byes };
+        match format.rounds() {
+            Ok(_) => break format,
+            Err(e) => println!("{}, please retry.", e),
+        }
+    };
+
+    let schedule = round_robin_schedule(roster, playoff)?;
+
+    fs::write(filepath, serde_json::to_string(&schedule)?)?;
+    info!("Saved matchup schedule to {}.", filepath);
+
+    Ok(schedule)
+}
+
+pub fn load(league_name: &str, roster: &Roster) -> Result<Schedule, Box<dyn Error>> {
+    if league_name == "sample" {
+        return sample_schedule(roster);
+    }
+
+    let filepath = format!(
+        "{}/.mlbh2h/leagues/{}/schedule.json",
+        utils::get_home_dir(),
+        league_name
+    );
+    if !Path::new(&filepath).exists() {
+        info!(
+            "No matchup schedule file {}, using a generated round-robin schedule",
+            filepath
+        );
+        let num_teams = team_names(roster).len();
+        let teams = if num_teams >= 4 { 4 } else { 2 };
+        return round_robin_schedule(roster, PlayoffFormat { teams, byes: 0 });
+    }
+
+    info!("Loading the matchup schedule from file {}", filepath);
+    let json = fs::read_to_string(filepath)?;
+    validate(serde_json::from_str(&json)?, roster)
+}
+
+/// Checks the playoff format of a schedule file, which may be edited by hand.
+fn validate(schedule: Schedule, roster: &Roster) -> Result<Schedule, Box<dyn Error>> {
+    schedule.playoff.rounds()?;
+    let num_teams = team_names(roster).len();
+    if schedule.playoff.teams > num_teams {
+        return Err(Box::new(TooManyPlayoffTeams(
+            schedule.playoff.teams,
+            num_teams,
+        )));
+    }
+    Ok(schedule)
+}
+
+pub fn sample_schedule(roster: &Roster) -> Result<Schedule, Box<dyn Error>> {
+    round_robin_schedule(roster, PlayoffFormat { teams: 2, byes: 0 })
+}
+
+pub fn team_names(roster: &Roster) -> Vec<String> {
+    let mut teams: Vec<String> = vec![];
+    roster.players.iter().for_each(|p| {
+        if !teams.iter().any(|t| t.as_str() == p.team.as_ref()) {
+            teams.push(p.team.to_string());
+        }
+    });
+    teams
+}
+
+/// Creates regular season matchups with the circle method, leaving the last weeks
+/// of the season for the playoff rounds.
+fn round_robin_schedule(
+    roster: &Roster,
+    playoff: PlayoffFormat,
+) -> Result<Schedule, Box<dyn Error>> {
+    let rounds = playoff.rounds()?;
+
+    let mut slots: Vec<Option<String>> = team_names(roster).into_iter().map(Some).collect();
+    if slots.len() % 2 == 1 {
+        slots.push(None);
+    }
+
+    let starts = utils::season_week_start_strs();
+    let regular_weeks = starts.len().saturating_sub(rounds);
+
+    let mut weeks = vec![];
+    for (i, start) in starts.iter().take(regular_weeks).enumerate() {
+        let end = match starts.get(i + 1) {
+            Some(next) => utils::add_days(next, -1),
+            None => utils::add_days(start, 6),
+        };
+
+        let n = slots.len();
+        let matchups = (0..n / 2)
+            .filter_map(|j| match (&slots[j], &slots[n - 1 - j]) {
+                (Some(a), Some(b)) if i % 2 == 0 => Some(Matchup {
+                    home: a.clone(),
+                    away: b.clone(),
+                }),
+                (Some(a), Some(b)) => Some(Matchup {
+                    home: b.clone(),
+                    away: a.clone(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        weeks.push(Week {
+            start: start.clone(),
+            end,
+            matchups,
+        });
+
+        if n > 2 {
+            // keep the first slot fixed and rotate the others
+            let last = slots.remove(n - 1);
+            slots.insert(1, last);
+        }
+    }
+
+    Ok(Schedule { weeks, playoff })
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+
+    #[test]
+    fn rounds_should_return_number_of_playoff_rounds() {
+        assert_eq!(1, PlayoffFormat { teams: 2, byes: 0 }.rounds().unwrap());
+        assert_eq!(2, PlayoffFormat { teams: 4, byes: 0 }.rounds().unwrap());
+        assert_eq!(3, PlayoffFormat { teams: 6, byes: 2 }.rounds().unwrap());
+        assert_eq!(3, PlayoffFormat { teams: 8, byes: 0 }.rounds().unwrap());
+        assert!(PlayoffFormat { teams: 6, byes: 0 }.rounds().is_err());
+        assert!(PlayoffFormat { teams: 5, byes: 2 }.rounds().is_err());
+    }
+
+    #[test]
+    fn validate_should_reject_playoff_format_not_playable_by_league() {
+        let roster = sample_roster();
+        let schedule = |teams, byes| Schedule {
+            weeks: vec![],
+            playoff: PlayoffFormat { teams, byes },
+        };
+        assert!(validate(schedule(4, 0), &roster).is_ok());
+        assert!(validate(schedule(8, 0), &roster).is_err());
+        assert!(validate(schedule(2, 2), &roster).is_err());
+        assert!(validate(schedule(4, 5), &roster).is_err());
+    }
+
+    #[test]
+    fn sample_schedule_should_match_every_team_once_a_week() {
+        let roster = sample_roster();
+        let schedule = sample_schedule(&roster).unwrap();
+
+        assert_eq!(25, schedule.weeks.len());
+        assert_eq!("2019-07-08", schedule.weeks[15].start);
+        assert_eq!("2019-07-21", schedule.weeks[15].end);
+
+        let teams = team_names(&roster);
+        for week in schedule.weeks.iter() {
+            assert_eq!(2, week.matchups.len());
+            for t in teams.iter() {
+                assert_eq!(
+                    1,
+                    week.matchups
+                        .iter()
+                        .filter(|m| &m.home == t || &m.away == t)
+                        .count()
+                );
+            }
+        }
+
+        let first_three: Vec<_> = schedule.weeks[..3]
+            .iter()
+            .map(|w| {
+                let m = &w.matchups[0];
+                if m.home == "LA Bulls" {
+                    m.away.as_str()
+                } else {
+                    m.home.as_str()
+                }
+            })
+            .collect();
+        assert_eq!(
+            vec!["Seattle Coffees", "NY Hotdogs", "Chicago Pizzas"],
+            first_three
+        );
+    }
+
+    #[test]
+    fn week_of_should_return_week_including_date() {
+        let schedule = sample_schedule(&sample_roster()).unwrap();
+
+        assert_eq!("2019-04-01", schedule.week_of("2019-04-03").unwrap().start);
+        assert_eq!("2019-07-08", schedule.week_of("2019-07-18").unwrap().start);
+        assert!(schedule.week_of("2019-09-28").is_none());
+    }
+}
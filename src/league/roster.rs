@@ -46,6 +46,13 @@ impl<'a> Player<'a> {
             team: Rc::new(team.into()),
         }
     }
+
+    pub fn is_pitcher(&self) -> bool {
+        match self.role {
+            PlayerType::Pitcher => true,
+            PlayerType::Batter => false,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
@@ -117,7 +124,11 @@ fn get_players_stdin<'a>(role: PlayerType, size: usize, team: Rc<Cow<'a, str>>)
     players
 }
 
-fn get_usize_stdin(label: &str, min: usize, max: usize) -> Result<usize, Box<dyn Error>> {
+pub(super) fn get_usize_stdin(
+    label: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize, Box<dyn Error>> {
     loop {
         let mut input_str = String::new();
         print!("{} ({}-{}) > ", label, min, max);
@@ -1,6 +1,7 @@
 use clap::{crate_version, App, Arg, SubCommand};
 use env_logger;
 use log::error;
+use std::env;
 use std::error::Error;
 
 mod league;
@@ -12,23 +13,39 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let matches = get_app().get_matches();
 
-    if let Some(m) = matches.subcommand_matches("new-league") {
-        if let Err(e) = league::add_new_league(m) {
-            error!("{}", e);
-            return Err(e);
-        }
-        return Ok(());
-    }
+    let no_color = match matches.subcommand() {
+        (_, Some(m)) => m.is_present("no-color"),
+        _ => matches.is_present("no-color"),
+    };
+    stats::set_color(
+        !no_color && atty::is(atty::Stream::Stdout) && env::var_os("NO_COLOR").is_none(),
+    );
 
-    if matches.subcommand_matches("list-leagues").is_some() {
-        if let Err(e) = league::list_leagues() {
-            error!("{}", e);
-            return Err(e);
-        }
-        return Ok(());
-    }
+    let result = match matches.subcommand() {
+        ("new-league", Some(m)) => league::add_new_league(m),
+        ("list-leagues", Some(_)) => league::list_leagues(),
+        ("playoffs", Some(m)) => stats::playoff::show(m),
+        ("waivers", Some(m)) => stats::waivers::show(m),
+        ("trade", Some(m)) => stats::trade::show(m),
+        ("streamers", Some(m)) => stats::streamers::show(m),
+        ("player", Some(m)) => stats::gamelog::show(m),
+        ("search", Some(m)) => stats::search::show(m),
+        ("refresh", Some(m)) => stats::refresh::show(m),
+        ("compare", Some(m)) => stats::compare::show(m),
+        ("trending", Some(m)) => stats::trending::show(m),
+        ("splits", Some(m)) => stats::splits::show(m),
+        ("points-allowed", Some(m)) => stats::allowed::show(m),
+        ("parks", Some(m)) => stats::parks::show(m),
+        ("breakdown", Some(m)) => stats::breakdown::show(m),
+        ("explain", Some(m)) => stats::explain::show(m),
+        ("report", Some(m)) => stats::report::show(m),
+        ("xlsx", Some(m)) => stats::xlsx::show(m),
+        #[cfg(feature = "export")]
+        ("export", Some(m)) => stats::export::show(m),
+        _ => stats::show(&matches),
+    };
 
-    if let Err(e) = stats::show(&matches) {
+    if let Err(e) = result {
         error!("{}", e);
         return Err(e);
     }
@@ -37,61 +54,11 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn get_app<'a, 'b>() -> App<'a, 'b> {
-    App::new("mlbh2h")
+    let app = App::new("mlbh2h")
         .version(&crate_version!()[..])
         .author("Soo Philip Jason Kim <philipjkim@gmail.com>")
         .about("This app Shows Fantasy Baseball Head-to-Head points by your scoring settings.")
-        .arg(
-            Arg::with_name("date")
-                .short("d")
-                .long("date")
-                .value_name("YYYY-MM-DD")
-                .help("Sets the date for stats")
-                .takes_value(true)
-                .default_value(utils::yesterday_str()),
-        )
-        .arg(
-            Arg::with_name("range")
-                .short("r")
-                .long("range")
-                .value_name("RANGE")
-                .help("Sets the range for stats (1d, 1w, 2w, 1m, all)")
-                .takes_value(true)
-                .default_value("1d"),
-        )
-        .arg(
-            Arg::with_name("league")
-                .short("l")
-                .long("league")
-                .value_name("LEAGUE_NAME")
-                .help("Sets the league name for scoring and roster")
-                .takes_value(true)
-                .default_value("sample"),
-        )
-        .arg(
-            Arg::with_name("api_key")
-                .short("k")
-                .long("apikey")
-                .value_name("SPORTRADAR_API_KEY")
-                .help(
-                    "Sets sportsradar API key.
-Get a free api key at https://developer.sportradar.com/
-if you don't have one yet.
-Environment variable `SPORTRADAR_API_KEY` should be set
-if you don't want to set this option.
-The option value precedes env.",
-                )
-                .takes_value(true),
-        )
-        .arg(
-            Arg::with_name("format")
-                .short("f")
-                .long("format")
-                .value_name("FORMAT")
-                .help("Sets the output format, available values: pretty, csv")
-                .takes_value(true)
-                .default_value("pretty"),
-        )
+        .args(&stats_args())
         .arg(
             Arg::with_name("all")
                 .short("a")
@@ -114,6 +81,13 @@ The option value precedes env.",
             .help("If set, fantasy points per team for the week are shown")
             .takes_value(false)
         )
+        .arg(
+            Arg::with_name("projections")
+            .short("p")
+            .long("projections")
+            .help("If set with -w, projected fantasy points for the rest of the week and matchup win probabilities are shown (recent form over --range, 2w if the range is 1d)")
+            .takes_value(false)
+        )
         .arg(
             Arg::with_name("outstanding")
             .short("o")
@@ -123,6 +97,51 @@ The option value precedes env.",
             .takes_value(true)
             .default_value("")
         )
+        .arg(
+            Arg::with_name("consistency")
+            .short("C")
+            .long("consistency")
+            .help("If set, consistency columns (games, mean, median, standard deviation, 10th/90th percentiles, boom/bust rates) of daily fantasy points over the range are shown")
+            .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("boom")
+            .long("boom")
+            .value_name("BATTER_PTS:PITCHER_PTS")
+            .help("Daily fantasy points at or above which a game is counted as a boom")
+            .takes_value(true)
+            .default_value("10:20")
+        )
+        .arg(
+            Arg::with_name("bust")
+            .long("bust")
+            .value_name("BATTER_PTS:PITCHER_PTS")
+            .help("Daily fantasy points at or below which a game is counted as a bust")
+            .takes_value(true)
+            .default_value("2:5")
+        )
+        .arg(
+            Arg::with_name("columns")
+            .long("columns")
+            .value_name("COLUMNS")
+            .help("Comma-separated columns of the player tables, e.g. +B.AB,-B.SB to add/remove columns or Player,FanPts,B.HR for only those columns (any stat, scoring or not)")
+            .takes_value(true)
+            .allow_hyphen_values(true)
+        )
+        .arg(
+            Arg::with_name("sort")
+            .long("sort")
+            .value_name("COLUMN[:asc|desc]")
+            .help("Sorts players by a column of the player tables or a consistency column (G, Mean, Median, SD, P10, P90, Boom%, Bust%). Player, Team and Pos are ascending and the others descending by default")
+            .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("where")
+            .long("where")
+            .value_name("EXPR")
+            .help("Shows only players matching the expression over the columns, e.g. 'team == \"LA Bulls\" && B.HR >= 2' or 'pos in (SP, RP) && FanPts > 20' (==, !=, <, <=, >, >=, in, &&, ||, !)")
+            .takes_value(true)
+        )
         .subcommand(
             SubCommand::with_name("new-league")
                 .about("adds a new league settings (scoring rules + rosters)")
@@ -144,4 +163,429 @@ The option value precedes env.",
                 ),
         )
         .subcommand(SubCommand::with_name("list-leagues").about("lists previously added leagues"))
+        .subcommand(
+            SubCommand::with_name("playoffs")
+                .about("simulates the rest of the season and shows playoff odds per team")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("simulations")
+                        .short("s")
+                        .long("simulations")
+                        .value_name("N")
+                        .help("Sets the number of simulated seasons")
+                        .takes_value(true)
+                        .default_value("10000"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("waivers")
+                .about("ranks free agents and suggests add/drop swaps for a fantasy team")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("team")
+                        .short("t")
+                        .long("team")
+                        .value_name("TEAM_NAME")
+                        .help("Sets the fantasy team to suggest swaps for")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .short("n")
+                        .long("limit")
+                        .value_name("N")
+                        .help("Sets the max number of free agents and swaps shown")
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .arg(
+                    Arg::with_name("per_game")
+                        .short("g")
+                        .long("per-game")
+                        .help("If set, players are ranked and compared by fantasy points per game")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("trade")
+                .about("analyzes a trade by fantasy points of the players and team rankings")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("side_a")
+                        .short("a")
+                        .long("side-a")
+                        .value_name("PLAYER,...")
+                        .help("Sets the players given up by one team")
+                        .takes_value(true)
+                        .use_delimiter(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("side_b")
+                        .short("b")
+                        .long("side-b")
+                        .value_name("PLAYER,...")
+                        .help("Sets the players given up by the other team")
+                        .takes_value(true)
+                        .use_delimiter(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("ranges")
+                        .short("R")
+                        .long("ranges")
+                        .value_name("RANGE,...")
+                        .help("Sets the ranges to compare (1d, 1w, 2w, 1m, all)")
+                        .takes_value(true)
+                        .use_delimiter(true)
+                        .default_value("2w,1m,all"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("streamers")
+                .about("lists upcoming probable starters and two-start pitchers of the week")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("days")
+                        .short("n")
+                        .long("days")
+                        .value_name("N")
+                        .help("Sets the number of days after --date to list probable starters (recent form over --range, 1m if the range is 1d)")
+                        .takes_value(true)
+                        .default_value("3"),
+                )
+                .arg(
+                    Arg::with_name("free_agents")
+                        .short("F")
+                        .long("free-agents")
+                        .help("If set, only free agent pitchers are shown")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("explain")
+                .about("explains the fantasy points of a player over the range stat by stat")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .help("Sets the player name (case insensitive)")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("report")
+                .about("generates a self-contained HTML report with sortable tables of team rankings, weekly changes, top performers and players")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Writes the report to the file instead of stdout")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("top")
+                        .short("t")
+                        .long("top")
+                        .value_name("N")
+                        .help("Sets the number of top batters/pitchers")
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .short("a")
+                        .long("all")
+                        .help("If set, FA players are also shown"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("xlsx")
+                .about("writes an Excel workbook with sheets of players, top batters, top pitchers, team rankings and weekly changes")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Sets the path of the workbook, e.g. week.xlsx")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("top")
+                        .short("t")
+                        .long("top")
+                        .value_name("N")
+                        .help("Sets the number of top batters/pitchers")
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .arg(
+                    Arg::with_name("all")
+                        .short("a")
+                        .long("all")
+                        .help("If set, FA players are also shown"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("breakdown")
+                .about("shows fantasy points of each team by rostered player and scoring category (--format pretty, csv, markdown, json or ndjson)")
+                .args(&stats_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("parks")
+                .about("shows park factors of runs, home runs and fantasy points relative to the league average over the range (all if the range is 1d)")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("min_games")
+                        .short("g")
+                        .long("min-games")
+                        .value_name("N")
+                        .help("Sets the min number of games played in a park to be shown")
+                        .takes_value(true)
+                        .default_value("1"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("points-allowed")
+                .about("ranks MLB teams by fantasy points allowed per game to opposing batters and pitchers over the range (all if the range is 1d)")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("role")
+                        .long("role")
+                        .value_name("ROLE")
+                        .help("Sets the opposing role to rank teams by")
+                        .takes_value(true)
+                        .possible_values(&["batters", "pitchers"])
+                        .default_value("pitchers"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("splits")
+                .about("shows home/away, day/night and by opponent splits of players over the range (all if the range is 1d)")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("names")
+                        .value_name("NAME")
+                        .help("Sets the player names (case insensitive)")
+                        .required(true)
+                        .multiple(true)
+                        .index(1),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("trending")
+                .about("lists players on hot and cold streaks by recent fantasy points per game against their baseline")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("window")
+                        .short("W")
+                        .long("window")
+                        .value_name("RANGE")
+                        .help("Sets the recent window compared against the rest of --range (all if the range is 1d)")
+                        .takes_value(true)
+                        .possible_values(&["1w", "2w", "1m"])
+                        .default_value("1w"),
+                )
+                .arg(
+                    Arg::with_name("min_z")
+                        .short("z")
+                        .long("min-z")
+                        .value_name("Z")
+                        .help("Sets the min z-score of recent points per game against the baseline to be flagged")
+                        .takes_value(true)
+                        .default_value("2.0"),
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .short("n")
+                        .long("limit")
+                        .value_name("N")
+                        .help("Sets the max number of hot and cold players shown")
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .arg(
+                    Arg::with_name("free_agents")
+                        .short("F")
+                        .long("free-agents")
+                        .help("If set, only free agents are shown")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("player")
+                .about("shows the game log of a player over the range")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .help("Sets the player name (case insensitive)")
+                        .required(true)
+                        .index(1),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("search")
+                .about("searches players in the stats cache by a part of (or misspelled) name")
+                .arg(
+                    Arg::with_name("query")
+                        .value_name("NAME")
+                        .help("Sets the name to search (case and accent insensitive)")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .short("n")
+                        .long("limit")
+                        .value_name("N")
+                        .help("Sets the max number of players shown")
+                        .takes_value(true)
+                        .default_value("10"),
+                )
+                .args(&stats_args_without_json()),
+        )
+        .subcommand(
+            SubCommand::with_name("refresh")
+                .about("refetches the cached stats missing MLB team, opponent, home/away, day/night or venue over the range (all if the range is 1d)")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("dry_run")
+                        .short("n")
+                        .long("dry-run")
+                        .help("If set, only lists the dates to be refetched")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("compare")
+                .about("compares stats and fantasy points of 2-5 players side by side")
+                .args(&stats_args_without_json())
+                .arg(
+                    Arg::with_name("names")
+                        .value_name("NAME")
+                        .help("Sets the player names (stats over --range, the season if the range is 1d, and last 7/14/30 days splits are shown)")
+                        .required(true)
+                        .min_values(2)
+                        .max_values(5)
+                        .index(1),
+                ),
+        );
+    #[cfg(feature = "export")]
+    let app = app.subcommand(export_subcommand());
+    app
+}
+
+#[cfg(feature = "export")]
+fn export_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("export")
+        .about("exports the stats cache as Parquet or Arrow IPC files with a row per player, a file per date not exported yet")
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .value_name("DIR")
+                .help("Sets the directory of the files, e.g. stats for stats/date=2019-08-25/part.parquet")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("f")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Sets the file format")
+                .takes_value(true)
+                .possible_values(&["parquet", "arrow"])
+                .default_value("parquet"),
+        )
+        .arg(
+            Arg::with_name("league")
+                .short("l")
+                .long("league")
+                .value_name("LEAGUE_NAME")
+                .help("If set, a fantasy_points column by the scoring settings of the league is added")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .help("If set, the files of all the cached dates are rewritten"),
+        )
+}
+
+fn stats_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    stats_args_with_formats(
+        &["pretty", "csv", "json", "ndjson", "markdown"],
+        "Sets the output format (json and ndjson are documented in README)",
+    )
+}
+
+/// `stats_args` for the subcommands without JSON output.
+fn stats_args_without_json<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    stats_args_with_formats(&["pretty", "csv", "markdown"], "Sets the output format")
+}
+
+fn stats_args_with_formats<'a, 'b>(
+    formats: &'a [&'a str],
+    format_help: &'a str,
+) -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("date")
+            .short("d")
+            .long("date")
+            .value_name("YYYY-MM-DD")
+            .help("Sets the date for stats")
+            .takes_value(true)
+            .default_value(utils::yesterday_str()),
+        Arg::with_name("range")
+            .short("r")
+            .long("range")
+            .value_name("RANGE")
+            .help("Sets the range for stats (1d, 1w, 2w, 1m, all)")
+            .takes_value(true)
+            .default_value("1d"),
+        Arg::with_name("league")
+            .short("l")
+            .long("league")
+            .value_name("LEAGUE_NAME")
+            .help("Sets the league name for scoring and roster")
+            .takes_value(true)
+            .default_value("sample"),
+        Arg::with_name("api_key")
+            .short("k")
+            .long("apikey")
+            .value_name("SPORTRADAR_API_KEY")
+            .help(
+                "Sets sportsradar API key.
+Get a free api key at https://developer.sportradar.com/
+if you don't have one yet.
+Environment variable `SPORTRADAR_API_KEY` should be set
+if you don't want to set this option.
+The option value precedes env.",
+            )
+            .takes_value(true),
+        Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .value_name("FORMAT")
+            .help(format_help)
+            .takes_value(true)
+            .possible_values(formats)
+            .default_value("pretty"),
+        no_color_arg(),
+    ]
+}
+
+fn no_color_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("no-color")
+        .long("no-color")
+        .help("If set, the pretty output is not colored (it is colored only if stdout is a terminal and NO_COLOR env is not set)")
+        .takes_value(false)
 }
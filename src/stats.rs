@@ -12,10 +12,37 @@ use std::fs;
 use std::ops::Add;
 use std::path::Path;
 use std::rc::Rc;
-
+use walkdir::WalkDir;
+
+pub mod allowed;
+pub mod breakdown;
+mod columns;
+pub mod compare;
+pub mod consistency;
+pub mod explain;
+#[cfg(feature = "export")]
+pub mod export;
+mod filter;
+mod format;
+pub mod gamelog;
+mod json;
 mod output;
+pub mod parks;
+pub mod playoff;
+mod projection;
+pub mod refresh;
+pub mod report;
 mod schedule;
+pub mod search;
+pub mod splits;
 mod sportradar;
+pub mod streamers;
+pub mod trade;
+pub mod trending;
+pub mod waivers;
+pub mod xlsx;
+
+pub use crate::stats::format::set_color;
 
 pub struct Config<'a> {
     date: Cow<'a, str>,
@@ -27,6 +54,7 @@ pub struct Config<'a> {
     top_n: usize,
     weekly_changes: bool,
     outstanding: Option<(f32, f32)>,
+    projections: bool,
 }
 impl<'a> Config<'a> {
     pub fn new<S>(
@@ -39,6 +67,7 @@ impl<'a> Config<'a> {
         top_n: usize,
         weekly_changes: bool,
         outstanding_str: S,
+        projections: bool,
     ) -> Config<'a>
     where
         S: Into<Cow<'a, str>>,
@@ -66,6 +95,7 @@ impl<'a> Config<'a> {
             top_n,
             weekly_changes,
             outstanding,
+            projections,
         }
     }
 }
@@ -73,6 +103,16 @@ impl<'a> Config<'a> {
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Player<'a> {
     name: Cow<'a, str>,
+    #[serde(default)]
+    mlb_team: Cow<'a, str>,
+    #[serde(default)]
+    opponent: Cow<'a, str>,
+    #[serde(default)]
+    home: Option<bool>,
+    #[serde(default)]
+    day_night: Cow<'a, str>,
+    #[serde(default)]
+    venue: Cow<'a, str>,
     position: Cow<'a, str>,
     primary_position: Cow<'a, str>,
     batter_stats: Option<BatterStats>,
@@ -91,6 +131,7 @@ impl<'a> Player<'a> {
             primary_position: primary_position.into(),
             batter_stats: Some(stats),
             pitcher_stats: None,
+            ..Default::default()
         }
     }
 
@@ -104,6 +145,7 @@ impl<'a> Player<'a> {
             primary_position: primary_position.into(),
             batter_stats: None,
             pitcher_stats: Some(stats),
+            ..Default::default()
         }
     }
 
@@ -228,6 +270,10 @@ pub struct FantasyPlayer<'a> {
     fantasy_points: f32,
 }
 impl<'a> FantasyPlayer<'a> {
+    fn key(&self) -> String {
+        player_key(&self.player.name, self.player.pitcher_stats.is_some())
+    }
+
     fn add_stats(&mut self, other: FantasyPlayer<'a>) {
         let player = other.player;
 
@@ -292,32 +338,70 @@ pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
     let league_scoring = scoring::load(&league)?;
     let league_roster = roster::load(&league)?;
 
+    let columns = columns::Options::from_matches(matches)?;
     if config.outstanding.is_some() {
-        return Ok(show_outstanding_players(&config, &league_scoring)?);
+        return show_outstanding_players(&config, &league_scoring, &columns);
     }
 
     if config.weekly_changes {
-        return Ok(show_weekly_changes(
+        show_weekly_changes(
             utils::weekly_date_strs(&config.date),
             &config,
             &league_scoring,
             &league_roster,
-        )?);
+        )?;
+        if config.projections && json::is_json(&config.format) {
+            warn!(
+                "projections are not available in the {} format",
+                config.format
+            );
+        } else if config.projections {
+            println!();
+            projection::show(&config, &league_scoring, &league_roster)?;
+        }
+        return Ok(());
     }
 
-    let players: Vec<_> = dates
+    let players_per_date: Vec<_> = dates
         .clone()
         .into_iter()
-        .flat_map(|d| players_for_date(d, &config))
+        .map(|d| players_for_date(d, &config))
         .collect();
+    let players: Vec<_> = players_per_date.iter().flatten().cloned().collect();
 
-    let fan_players =
+    let mut fan_players =
         create_fantasy_players(&players, &league_scoring, &league_roster, config.show_all)?;
 
+    let consistency =
+        match consistency::Options::from_matches(matches, columns.sorts_by_consistency())? {
+            Some(options) => {
+                let per_date = players_per_date
+                    .iter()
+                    .map(|ps| {
+                        create_fantasy_players(ps, &league_scoring, &league_roster, config.show_all)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                consistency::consistency_per_player(&per_date, &options)
+            }
+            None => HashMap::new(),
+        };
+    columns.sort(&mut fan_players, &consistency);
+
+    if json::is_json(&config.format) {
+        return json::print_fantasy_players(&fan_players, &config, &consistency, &columns);
+    }
+    let f = format::formatter(&config.format)?;
     println!();
-    output::print_fantasy_players(fan_players.clone(), &config, &league_scoring);
+    output::print_fantasy_players(
+        fan_players.clone(),
+        &config,
+        &league_scoring,
+        &consistency,
+        &columns,
+        f.as_ref(),
+    );
     println!();
-    output::print_scores_per_team(fan_players, &config.format == "csv");
+    output::print_scores_per_team(fan_players, f.as_ref());
 
     Ok(())
 }
@@ -343,115 +427,187 @@ fn players_for_date<'a>(date: String, config: &Config) -> Vec<Player<'a>> {
 fn show_outstanding_players(
     config: &Config,
     s: &scoring::ScoringRule,
+    columns: &columns::Options,
 ) -> Result<(), Box<dyn Error>> {
     let dates = utils::date_strs(&config.date, "all");
     let batter_threshold = config.outstanding.unwrap().0;
     let pitcher_threshold = config.outstanding.unwrap().1;
 
-    dates.into_iter().for_each(|d| {
-        let players = players_for_date(d.clone(), config);
-        let fplayers =
-            create_fantasy_players(&players, s, &roster::Roster { players: vec![] }, true).unwrap();
+    let players_per_date: Vec<_> = dates
+        .into_iter()
+        .map(|d| (d.clone(), players_for_date(d, config)))
+        .collect();
+    let mut outstanding = vec![];
+    players_per_date.iter().for_each(|(d, players)| {
+        let mut fplayers =
+            create_fantasy_players(players, s, &roster::Roster { players: vec![] }, true).unwrap();
+        columns.sort(&mut fplayers, &HashMap::new());
 
-        fplayers.into_iter().for_each(|fp| {
-            if (fp.player.batter_stats.is_some() && fp.fantasy_points >= batter_threshold)
-                || (fp.player.pitcher_stats.is_some() && fp.fantasy_points >= pitcher_threshold)
-            {
-                output::print_outstanding_player(d.clone(), fp, s);
-            }
-        });
+        fplayers
+            .into_iter()
+            .filter(|fp| columns.keep(fp))
+            .for_each(|fp| {
+                if (fp.player.batter_stats.is_some() && fp.fantasy_points >= batter_threshold)
+                    || (fp.player.pitcher_stats.is_some() && fp.fantasy_points >= pitcher_threshold)
+                {
+                    outstanding.push((d.clone(), fp));
+                }
+            });
     });
+
+    if json::is_json(&config.format) {
+        return json::print_outstanding_players(&outstanding, config);
+    }
+    let f = format::formatter(&config.format)?;
+    outstanding
+        .into_iter()
+        .for_each(|(d, fp)| output::print_outstanding_player(d, fp, s, columns, f.as_ref()));
     Ok(())
 }
 
-fn show_weekly_changes<'a>(
+/// Fantasy points per team of each date and the totals, in the order of team names.
+type WeeklyChanges = (Vec<(String, Vec<(String, f32)>)>, Vec<(String, f32)>);
+
+fn weekly_changes<'a>(
     dates: Vec<String>,
     config: &Config,
     s: &scoring::ScoringRule,
     r: &roster::Roster<'a>,
-) -> Result<(), Box<dyn Error>> {
-    let is_csv = config.format == "csv";
+) -> WeeklyChanges {
     let mut teams: Vec<_> = r
         .players
         .clone()
         .into_iter()
-        .map(|x| x.team.clone())
+        .map(|x| x.team.to_string())
         .collect();
-    teams.dedup();
     teams.sort();
-    let header = if is_csv {
-        teams
-            .iter()
-            .fold(format!("{}", "Date"), |acc, x| format!("{},{}", acc, x))
-    } else {
-        teams.iter().fold(format!("{:12}", "Date"), |acc, x| {
-            format!("{}{:>10}", acc, x)
+    teams.dedup();
+
+    let mut totals: Vec<(String, f32)> = teams.iter().map(|t| (t.clone(), 0.0)).collect();
+    let changes = dates
+        .into_iter()
+        .map(|d| {
+            let mut fpts: Vec<_> = fantasy_points_per_team(&d, config, s, r)
+                .into_iter()
+                .filter(|(team, _)| teams.contains(team))
+                .collect();
+            teams.iter().for_each(|t| {
+                if !fpts.iter().any(|(team, _)| *t == *team) {
+                    fpts.push((t.to_string(), 0.0));
+                }
+            });
+            fpts.sort_by(|a, b| a.0.cmp(&b.0));
+            fpts.iter().zip(totals.iter_mut()).for_each(|(x, total)| {
+                total.1 += x.1;
+            });
+            (d, fpts)
         })
+        .collect();
+
+    (changes, totals)
+}
+
+/// Returns a row of the points per team by date and the row of the totals, with the points
+/// formatted by `cell`.
+fn weekly_change_rows(
+    (changes, totals): &WeeklyChanges,
+    cell: fn(f32) -> String,
+) -> (Vec<Vec<String>>, Vec<String>) {
+    let row = |first: &str, pts: &[(String, f32)]| {
+        std::iter::once(first.to_string())
+            .chain(pts.iter().map(|(_, p)| cell(*p)))
+            .collect::<Vec<_>>()
     };
-    println!("{}", header);
+    (
+        changes.iter().map(|(d, pts)| row(d, pts)).collect(),
+        row("Total", totals),
+    )
+}
 
-    let mut total_pts = vec![0.0; teams.len()];
+/// Players over the range, the top batters and pitchers, team rankings and weekly changes
+/// of the report and the workbook.
+struct ReportData<'a> {
+    players: Vec<FantasyPlayer<'a>>,
+    top_batters: Vec<FantasyPlayer<'a>>,
+    top_pitchers: Vec<FantasyPlayer<'a>>,
+    scores: Vec<(String, f32)>,
+    weekly_changes: WeeklyChanges,
+}
 
-    dates.into_iter().for_each(|d| {
-        let players = players_for_date(d.clone(), config);
-        let fplayers = create_fantasy_players(&players, s, r, false).unwrap();
-        let mut fpts: Vec<_> = fplayers
-            .into_iter()
-            .fold(HashMap::new(), |mut acc, x| {
-                let fpts = acc.entry(x.team.clone()).or_insert(0.0);
-                *fpts += x.fantasy_points;
-                acc
-            })
-            .into_iter()
-            .map(|(k, v)| (k, v))
-            .collect();
-        teams.iter().for_each(|t| {
-            if !fpts.iter().any(|(team, _)| t == team) {
-                fpts.push((t.clone(), 0.0));
-            }
-        });
-        fpts.sort_by(|a, b| a.0.cmp(&b.0));
-        let body = fpts
-            .into_iter()
-            .inspect(|x| {
-                let idx = teams.iter().position(|t| *t == x.0).unwrap();
-                total_pts[idx] += x.1;
-            })
-            .fold(
-                if is_csv {
-                    format!("{}", d)
-                } else {
-                    format!("{:12}", d)
-                },
-                |acc, x| {
-                    if is_csv {
-                        format!("{},{}", acc, x.1)
-                    } else {
-                        format!("{}{:10.1}", acc, x.1)
-                    }
-                },
-            );
-        println!("{}", body);
-    });
+fn report_data<'a>(
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster<'a>,
+    top_n: usize,
+) -> Result<ReportData<'a>, Box<dyn Error>> {
+    let players: Vec<_> = utils::date_strs(&config.date, &config.range)
+        .into_iter()
+        .flat_map(|d| players_for_date(d, config))
+        .collect();
+    let players = create_fantasy_players(&players, s, r, config.show_all)?;
+    let (top_batters, top_pitchers) = output::top_players(players.clone(), top_n);
+    let scores = output::get_scores_per_team(&players);
+    let weekly_changes = weekly_changes(utils::weekly_date_strs(&config.date), config, s, r);
+
+    Ok(ReportData {
+        players,
+        top_batters,
+        top_pitchers,
+        scores,
+        weekly_changes,
+    })
+}
 
-    if is_csv {
-        print!("{}", "Total");
-        total_pts.into_iter().for_each(|p| {
-            print!(",{}", p);
-        });
-    } else {
-        print!("{:12}", "Total");
-        total_pts.into_iter().for_each(|p| {
-            print!("{:10.1}", p);
-        });
+fn show_weekly_changes<'a>(
+    dates: Vec<String>,
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster<'a>,
+) -> Result<(), Box<dyn Error>> {
+    let weekly = weekly_changes(dates, config, s, r);
+    if json::is_json(&config.format) {
+        return json::print_weekly_changes(&weekly.0, &weekly.1, config);
     }
-    println!();
+    let f = format::formatter(&config.format)?;
+    let mut columns = vec![format::Column::new("Date", 12)];
+    columns.extend(
+        weekly
+            .1
+            .iter()
+            .map(|(t, _)| format::Column::new(t, 11).right().points()),
+    );
+    // CSV has the points as they are, and the others rounded to a decimal place
+    let cell: fn(f32) -> String = if config.format == "csv" {
+        |p| p.to_string()
+    } else {
+        |p| format!("{:.1}", p)
+    };
+    let (mut rows, total) = weekly_change_rows(&weekly, cell);
+    rows.push(total);
+
+    println!("{}", f.heading(1, "Weekly Changes"));
+    println!("{}", f.table(&columns, &rows));
 
     Ok(())
 }
 
+fn fantasy_points_per_team(
+    date: &str,
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> HashMap<String, f32> {
+    let players = players_for_date(date.to_string(), config);
+    let fplayers = create_fantasy_players(&players, s, r, false).unwrap();
+    fplayers.into_iter().fold(HashMap::new(), |mut acc, x| {
+        let fpts = acc.entry(x.team.to_string()).or_insert(0.0);
+        *fpts += x.fantasy_points;
+        acc
+    })
+}
+
 fn create_fantasy_players<'a>(
-    players: &'a [Player],
+    players: &[Player<'a>],
     s: &scoring::ScoringRule,
     r: &roster::Roster<'a>,
     show_all: bool,
@@ -462,7 +618,6 @@ fn create_fantasy_players<'a>(
         .map(|p| (p.name.to_owned(), p.role))
         .collect();
 
-    let team_fa = Rc::new(Cow::Borrowed("<FA>"));
     let players: Vec<FantasyPlayer> = players
         .iter()
         .filter(|p| {
@@ -474,21 +629,7 @@ fn create_fantasy_players<'a>(
                 })
             }
         })
-        .map(|p| {
-            let team = r
-                .players
-                .iter()
-                .find(|rp| {
-                    (rp.name.to_lowercase() == p.name.to_lowercase()) && p.is_position_of(rp.role)
-                })
-                .map(|rp| Rc::clone(&rp.team))
-                .unwrap_or_else(|| Rc::clone(&team_fa));
-            FantasyPlayer {
-                team,
-                fantasy_points: get_fantasy_points(&p, s),
-                player: p.clone(),
-            }
-        })
+        .map(|p| to_fantasy_player(p, s, r))
         .collect();
 
     let players = merge_same_players_stats(players);
@@ -496,16 +637,46 @@ fn create_fantasy_players<'a>(
     Ok(sort_by_fantasy_points(players))
 }
 
+/// The fantasy team of the players not in the roster.
+pub const FA: &str = "<FA>";
+
+thread_local! {
+    /// Shared by the free agents, which are most of the players.
+    static FA_TEAM: Rc<Cow<'static, str>> = Rc::new(Cow::Borrowed(FA));
+}
+
+/// Returns the fantasy player of given player, whose team is `FA` if not in the roster.
+fn to_fantasy_player<'a>(
+    p: &Player<'a>,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster<'a>,
+) -> FantasyPlayer<'a> {
+    let team = r
+        .players
+        .iter()
+        .find(|rp| (rp.name.to_lowercase() == p.name.to_lowercase()) && p.is_position_of(rp.role))
+        .map(|rp| Rc::clone(&rp.team))
+        .unwrap_or_else(|| FA_TEAM.with(Rc::clone));
+    FantasyPlayer {
+        team,
+        fantasy_points: get_fantasy_points(p, s),
+        player: p.clone(),
+    }
+}
+
+/// Returns the key of a player by the name (case-insensitively) and the role, e.g. `trey mancini:b`.
+fn player_key(name: &str, pitcher: bool) -> String {
+    format!(
+        "{}:{}",
+        name.to_lowercase(),
+        if pitcher { "p" } else { "b" }
+    )
+}
+
 fn merge_same_players_stats<'a>(players: Vec<FantasyPlayer<'a>>) -> Vec<FantasyPlayer<'a>> {
     let mut map = HashMap::<String, FantasyPlayer<'a>>::new();
     let map = players.into_iter().fold(&mut map, |m, p| {
-        let position = if p.player.pitcher_stats.is_some() {
-            "p"
-        } else {
-            "b"
-        };
-        let key = p.player.name.to_string() + ":" + position;
-        m.entry(key)
+        m.entry(p.key())
             .and_modify(|x| {
                 // TODO: remove data cloning (p.clone())
                 x.add_stats(p.clone());
@@ -525,48 +696,93 @@ fn sort_by_fantasy_points<'a>(mut players: Vec<FantasyPlayer<'a>>) -> Vec<Fantas
 }
 
 pub fn get_fantasy_points(p: &Player, s: &scoring::ScoringRule) -> f32 {
+    get_points_by_category(p, s)
+        .iter()
+        .fold(0.0, |acc, c| acc + c.points)
+}
+
+/// Fantasy points from a stat, e.g. 2 home runs (stat) * 4.0 (multiplier) = 8.0 points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryPoints {
+    pub category: &'static str,
+    pub stat: f32,
+    pub multiplier: f32,
+    pub points: f32,
+}
+impl CategoryPoints {
+    fn new(category: &'static str, stat: u32, multiplier: f32) -> CategoryPoints {
+        CategoryPoints {
+            category,
+            stat: stat as f32,
+            multiplier,
+            points: stat as f32 * multiplier,
+        }
+    }
+}
+
+/// Returns the fantasy points of each scoring category in the order of the header items,
+/// including the categories without points.
+pub fn get_points_by_category(p: &Player, s: &scoring::ScoringRule) -> Vec<CategoryPoints> {
     if let Some(stats) = &p.batter_stats {
-        return (stats.at_bats as f32 * s.batter.at_bats)
-            + (stats.runs as f32 * s.batter.runs)
-            + (stats.hits as f32 * s.batter.hits)
-            + (stats.singles as f32 * s.batter.singles)
-            + (stats.doubles as f32 * s.batter.doubles)
-            + (stats.triples as f32 * s.batter.triples)
-            + (stats.home_runs as f32 * s.batter.home_runs)
-            + (stats.runs_batted_in as f32 * s.batter.runs_batted_in)
-            + (stats.sacrifice_hits as f32 * s.batter.sacrifice_hits)
-            + (stats.stolen_bases as f32 * s.batter.stolen_bases)
-            + (stats.caught_stealing as f32 * s.batter.caught_stealing)
-            + (stats.walks as f32 * s.batter.walks)
-            + (stats.intentional_walks as f32 * s.batter.intentional_walks)
-            + (stats.hit_by_pitch as f32 * s.batter.hit_by_pitch)
-            + (stats.strikeouts as f32 * s.batter.strikeouts)
-            + (stats.ground_into_double_play as f32 * s.batter.ground_into_double_play)
-            + (stats.total_bases as f32 * s.batter.total_bases);
+        let b = &s.batter;
+        return vec![
+            CategoryPoints::new("B.AB", stats.at_bats, b.at_bats),
+            CategoryPoints::new("B.R", stats.runs, b.runs),
+            CategoryPoints::new("B.H", stats.hits, b.hits),
+            CategoryPoints::new("B.1B", stats.singles, b.singles),
+            CategoryPoints::new("B.2B", stats.doubles, b.doubles),
+            CategoryPoints::new("B.3B", stats.triples, b.triples),
+            CategoryPoints::new("B.HR", stats.home_runs, b.home_runs),
+            CategoryPoints::new("B.RBI", stats.runs_batted_in, b.runs_batted_in),
+            CategoryPoints::new("B.SAC", stats.sacrifice_hits, b.sacrifice_hits),
+            CategoryPoints::new("B.SB", stats.stolen_bases, b.stolen_bases),
+            CategoryPoints::new("B.CS", stats.caught_stealing, b.caught_stealing),
+            CategoryPoints::new("B.BB", stats.walks, b.walks),
+            CategoryPoints::new("B.IBB", stats.intentional_walks, b.intentional_walks),
+            CategoryPoints::new("B.HBP", stats.hit_by_pitch, b.hit_by_pitch),
+            CategoryPoints::new("B.K", stats.strikeouts, b.strikeouts),
+            CategoryPoints::new(
+                "B.GIDP",
+                stats.ground_into_double_play,
+                b.ground_into_double_play,
+            ),
+            CategoryPoints::new("B.TB", stats.total_bases, b.total_bases),
+        ];
     }
 
     if let Some(stats) = &p.pitcher_stats {
-        return inning_score(stats.innings_pitched, s.pitcher.innings_pitched)
-            + (stats.wins as f32 * s.pitcher.wins)
-            + (stats.losses as f32 * s.pitcher.losses)
-            + (stats.complete_games as f32 * s.pitcher.complete_games)
-            + (stats.shutouts as f32 * s.pitcher.shutouts)
-            + (stats.saves as f32 * s.pitcher.saves)
-            + (stats.outs as f32 * s.pitcher.outs)
-            + (stats.hits as f32 * s.pitcher.hits)
-            + (stats.earned_runs as f32 * s.pitcher.earned_runs)
-            + (stats.home_runs as f32 * s.pitcher.home_runs)
-            + (stats.walks as f32 * s.pitcher.walks)
-            + (stats.intentional_walks as f32 * s.pitcher.intentional_walks)
-            + (stats.hit_batters as f32 * s.pitcher.hit_batters)
-            + (stats.strikeouts as f32 * s.pitcher.strikeouts)
-            + (stats.stolen_bases_allowed as f32 * s.pitcher.stolen_bases_allowed)
-            + (stats.batters_grounded_into_double_plays as f32
-                * s.pitcher.batters_grounded_into_double_plays)
-            + (stats.total_bases_allowed as f32 * s.pitcher.total_bases_allowed);
-    }
-
-    0.0
+        let ps = &s.pitcher;
+        return vec![
+            CategoryPoints {
+                category: "P.IP",
+                stat: stats.innings_pitched,
+                multiplier: ps.innings_pitched,
+                points: inning_score(stats.innings_pitched, ps.innings_pitched),
+            },
+            CategoryPoints::new("P.W", stats.wins, ps.wins),
+            CategoryPoints::new("P.L", stats.losses, ps.losses),
+            CategoryPoints::new("P.CG", stats.complete_games, ps.complete_games),
+            CategoryPoints::new("P.SHO", stats.shutouts, ps.shutouts),
+            CategoryPoints::new("P.SV", stats.saves, ps.saves),
+            CategoryPoints::new("P.OUT", stats.outs, ps.outs),
+            CategoryPoints::new("P.H", stats.hits, ps.hits),
+            CategoryPoints::new("P.ER", stats.earned_runs, ps.earned_runs),
+            CategoryPoints::new("P.HR", stats.home_runs, ps.home_runs),
+            CategoryPoints::new("P.BB", stats.walks, ps.walks),
+            CategoryPoints::new("P.IBB", stats.intentional_walks, ps.intentional_walks),
+            CategoryPoints::new("P.HBP", stats.hit_batters, ps.hit_batters),
+            CategoryPoints::new("P.K", stats.strikeouts, ps.strikeouts),
+            CategoryPoints::new("P.SB", stats.stolen_bases_allowed, ps.stolen_bases_allowed),
+            CategoryPoints::new(
+                "P.GIDP",
+                stats.batters_grounded_into_double_plays,
+                ps.batters_grounded_into_double_plays,
+            ),
+            CategoryPoints::new("P.TB", stats.total_bases_allowed, ps.total_bases_allowed),
+        ];
+    }
+
+    vec![]
 }
 
 fn inning_score(inning_pitched: f32, score: f32) -> f32 {
@@ -633,6 +849,11 @@ fn convert_players<'a>(
 
         players.push(Player {
             name: Cow::Owned(format!("{} {}", srp.preferred_name, srp.last_name)),
+            mlb_team: Cow::Owned(srp.mlb_team.to_owned()),
+            opponent: Cow::Owned(srp.opponent.to_owned()),
+            home: srp.home,
+            day_night: Cow::Owned(srp.day_night.to_owned()),
+            venue: Cow::Owned(srp.venue.to_owned()),
             position: Cow::Owned(srp.position.to_owned()),
             primary_position: Cow::Owned(srp.primary_position.to_owned()),
             batter_stats: bs,
@@ -647,7 +868,10 @@ fn save_players(filepath: &str, players: &[Player]) -> Result<(), Box<dyn Error>
     if Path::new(filepath).exists() {
         return Err(Box::new(StatFileExists(filepath.to_string())));
     }
+    write_players(filepath, players)
+}
 
+fn write_players(filepath: &str, players: &[Player]) -> Result<(), Box<dyn Error>> {
     fs::create_dir_all(format!("{}/.mlbh2h/stats", utils::get_home_dir()))?;
     fs::write(filepath, serde_json::to_string(players)?)?;
     info!("Saved player stats to {} .", filepath);
@@ -655,6 +879,29 @@ fn save_players(filepath: &str, players: &[Player]) -> Result<(), Box<dyn Error>
     Ok(())
 }
 
+/// Returns the dates and the paths of the cached stats files sorted by date, none if nothing
+/// is cached yet.
+fn cached_stats_files() -> Vec<(String, String)> {
+    let stats_dir = format!("{}/.mlbh2h/stats", utils::get_home_dir());
+    let mut files: Vec<(String, String)> = WalkDir::new(&stats_dir)
+        .min_depth(1)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            match path.file_stem().and_then(|s| s.to_str()) {
+                Some(d) if path.extension().and_then(|e| e.to_str()) == Some("json") => {
+                    Some((d.to_string(), path.to_string_lossy().to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+    files.sort();
+    files
+}
+
 fn get_players_from_file(filepath: &str) -> Result<Vec<Player>, Box<dyn Error>> {
     info!("Loading players from file {}", filepath);
     let json = fs::read_to_string(filepath)?;
@@ -683,7 +930,8 @@ fn get_config<'a>(
         matches.occurrences_of("all") > 0,
         (matches.occurrences_of("topn") * 10) as usize,
         matches.occurrences_of("weekly-changes") > 0,
-        matches.value_of("outstanding").unwrap(),
+        matches.value_of("outstanding").unwrap_or(""),
+        matches.occurrences_of("projections") > 0,
     ))
 }
 
@@ -826,6 +1074,25 @@ mod test {
         assert_eq_f32(32.5, get_fantasy_points(&pitcher, &sr));
     }
 
+    #[test]
+    fn get_points_by_category_should_return_points_per_category() {
+        use crate::league::scoring::sample_scoring_rule;
+        let sr = sample_scoring_rule();
+
+        let categories = get_points_by_category(&mock_pitcher(), &sr);
+        assert_eq!(17, categories.len());
+        assert_eq!("P.IP", categories[0].category);
+        assert_eq_f32(
+            inning_score(categories[0].stat, categories[0].multiplier),
+            categories[0].points,
+        );
+        assert_eq_f32(
+            get_fantasy_points(&mock_pitcher(), &sr),
+            categories.iter().map(|c| c.points).sum(),
+        );
+        assert!(get_points_by_category(&Player::default(), &sr).is_empty());
+    }
+
     #[test]
     fn sort_by_fantasy_points_should_sort_players_by_fan_pts_descending() {
         let mut p1: FantasyPlayer = Default::default();
@@ -850,6 +1117,29 @@ mod test {
         assert_eq_f32(11.0, inning_score(3.2, 3.0));
     }
 
+    #[test]
+    fn weekly_change_rows_should_format_points_and_totals() {
+        let weekly = (
+            vec![(
+                "2019-08-19".to_string(),
+                vec![
+                    ("LA Bulls".to_string(), 10.5),
+                    ("NY Hotdogs".to_string(), -2.0),
+                ],
+            )],
+            vec![
+                ("LA Bulls".to_string(), 10.5),
+                ("NY Hotdogs".to_string(), -2.0),
+            ],
+        );
+        let (rows, total) = weekly_change_rows(&weekly, |p| format!("{:.2}", p));
+        assert_eq!(vec![vec!["2019-08-19", "10.50", "-2.00"]], rows);
+        assert_eq!(vec!["Total", "10.50", "-2.00"], total);
+
+        let (rows, _) = weekly_change_rows(&weekly, |p| p.to_string());
+        assert_eq!(vec![vec!["2019-08-19", "10.5", "-2"]], rows);
+    }
+
     #[test]
     fn create_fantasy_players_should_apply_to_names_case_insensitive() {
         use crate::league::scoring::sample_scoring_rule;
@@ -0,0 +1,244 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, Config, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+
+/// Fantasy points given up by an MLB team to the opposing batters and pitchers.
+#[derive(Debug, Default, Clone)]
+pub(super) struct Allowed {
+    pub(super) team: String,
+    games: usize,
+    to_batters: f32,
+    to_pitchers: f32,
+}
+impl Allowed {
+    fn to_batters_per_game(&self) -> f32 {
+        self.to_batters / self.games as f32
+    }
+
+    pub(super) fn to_pitchers_per_game(&self) -> f32 {
+        self.to_pitchers / self.games as f32
+    }
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let role = matches.value_of("role").unwrap();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let per_date =
+        fantasy_players_per_date(&utils::date_strs(&config.date, range), &config, &s, &r);
+
+    let mut allowed = points_allowed(&per_date);
+    allowed.sort_by(|a, b| match role {
+        "batters" => b
+            .to_batters_per_game()
+            .partial_cmp(&a.to_batters_per_game())
+            .unwrap(),
+        _ => b
+            .to_pitchers_per_game()
+            .partial_cmp(&a.to_pitchers_per_game())
+            .unwrap(),
+    });
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Fantasy Points Allowed to {} ({}, until {})",
+                if role == "batters" {
+                    "Batters"
+                } else {
+                    "Pitchers"
+                },
+                range,
+                config.date
+            )
+        )
+    );
+    print_allowed(&allowed, f.as_ref());
+
+    Ok(())
+}
+
+/// Returns the fantasy players of each date, a player per game.
+pub(super) fn fantasy_players_per_date<'a>(
+    dates: &[String],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster<'a>,
+) -> Vec<(String, Vec<FantasyPlayer<'a>>)> {
+    dates
+        .iter()
+        .map(|d| {
+            let players = stats::players_for_date(d.clone(), config);
+            let fps = players
+                .iter()
+                .map(|p| stats::to_fantasy_player(p, s, r))
+                .collect();
+            (d.clone(), fps)
+        })
+        .collect()
+}
+
+/// Sums the fantasy points of the players by their opponent. Games of a doubleheader
+/// are counted as a game, and players cached without opponent are left out.
+pub(super) fn points_allowed(per_date: &[(String, Vec<FantasyPlayer>)]) -> Vec<Allowed> {
+    let mut allowed: HashMap<String, Allowed> = HashMap::new();
+    let mut games: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+    for (date, fps) in per_date.iter() {
+        fps.iter()
+            .filter(|fp| !fp.player.opponent.is_empty())
+            .for_each(|fp| {
+                let opponent = fp.player.opponent.to_string();
+                let a = allowed.entry(opponent.clone()).or_insert_with(|| Allowed {
+                    team: opponent.clone(),
+                    ..Default::default()
+                });
+                if fp.player.pitcher_stats.is_some() {
+                    a.to_pitchers += fp.fantasy_points;
+                } else {
+                    a.to_batters += fp.fantasy_points;
+                }
+                games.entry(opponent).or_default().insert(date.clone());
+            });
+    }
+
+    allowed
+        .into_values()
+        .map(|mut a| {
+            a.games = games.get(&a.team).map(|g| g.len()).unwrap_or(0);
+            a
+        })
+        .filter(|a| a.games > 0)
+        .collect()
+}
+
+/// Returns the league averages of the points per game allowed to batters and to pitchers.
+pub(super) fn averages(allowed: &[Allowed]) -> (f32, f32) {
+    let games = allowed.iter().map(|a| a.games).sum::<usize>().max(1) as f32;
+    (
+        allowed.iter().map(|a| a.to_batters).sum::<f32>() / games,
+        allowed.iter().map(|a| a.to_pitchers).sum::<f32>() / games,
+    )
+}
+
+fn print_allowed(allowed: &[Allowed], f: &dyn Formatter) {
+    if allowed.is_empty() {
+        println!("No stats with opponents found in the stats cache.");
+        return;
+    }
+
+    let (avg_to_batters, avg_to_pitchers) = averages(allowed);
+    let index = |per_game: f32, avg: f32| {
+        if avg > 0.0 {
+            per_game / avg * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let columns = [
+        Column::new("#", 5).right(),
+        Column::new("Team", 6),
+        Column::new("G", 5).right(),
+        Column::new("BatPts/G", 10).right(),
+        Column::new("Idx", 7).right(),
+        Column::new("PitPts/G", 10).right(),
+        Column::new("Idx", 7).right(),
+    ];
+    let rows: Vec<Vec<String>> = allowed
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let (to_batters, to_pitchers) = (a.to_batters_per_game(), a.to_pitchers_per_game());
+            vec![
+                (i + 1).to_string(),
+                a.team.clone(),
+                a.games.to_string(),
+                format!("{:.2}", to_batters),
+                format!("{:.0}", index(to_batters, avg_to_batters)),
+                format!("{:.2}", to_pitchers),
+                format!("{:.0}", index(to_pitchers, avg_to_pitchers)),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, PitcherStats, Player};
+    use crate::utils::assert_eq_f32;
+    use std::borrow::Cow;
+
+    #[test]
+    fn points_allowed_should_sum_points_by_opponent_and_role() {
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+        let fp = |mut p: Player<'static>, opponent: &'static str| {
+            p.opponent = Cow::Borrowed(opponent);
+            stats::to_fantasy_player(&p, &s, &r)
+        };
+        let batter = || {
+            Player::new_batter(
+                "Cody Bellinger",
+                "OF",
+                "RF",
+                BatterStats {
+                    home_runs: 1,
+                    ..Default::default()
+                },
+            )
+        };
+        let pitcher = || {
+            Player::new_pitcher(
+                "Max Scherzer",
+                "P",
+                "SP",
+                PitcherStats {
+                    strikeouts: 10,
+                    ..Default::default()
+                },
+            )
+        };
+        let bat_pts = fp(batter(), "SF").fantasy_points;
+        let pit_pts = fp(pitcher(), "SF").fantasy_points;
+
+        let per_date = vec![
+            (
+                "2019-07-01".to_string(),
+                vec![fp(batter(), "SF"), fp(batter(), "SF"), fp(pitcher(), "SF")],
+            ),
+            (
+                "2019-07-02".to_string(),
+                vec![fp(batter(), "SF"), fp(pitcher(), "")],
+            ),
+        ];
+
+        let allowed = points_allowed(&per_date);
+        assert_eq!(1, allowed.len());
+        assert_eq!("SF", allowed[0].team);
+        assert_eq!(2, allowed[0].games);
+        assert_eq_f32(bat_pts * 1.5, allowed[0].to_batters_per_game());
+        assert_eq_f32(pit_pts / 2.0, allowed[0].to_pitchers_per_game());
+    }
+}
@@ -0,0 +1,375 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, json, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use serde::Serialize;
+use std::error::Error;
+
+/// Fantasy points of a fantasy team by player and by scoring category.
+#[derive(Serialize, Debug, Default, Clone)]
+struct TeamBreakdown {
+    team: String,
+    total: f32,
+    batting: f32,
+    pitching: f32,
+    players: Vec<Contribution>,
+    categories: Vec<CategoryTotal>,
+}
+
+#[derive(Serialize, Debug, Default, Clone)]
+struct Contribution {
+    name: String,
+    position: String,
+    pitcher: bool,
+    games: usize,
+    fantasy_points: f32,
+    share: f32,
+}
+
+#[derive(Serialize, Debug, Default, Clone)]
+struct CategoryTotal {
+    category: String,
+    stat: f32,
+    fantasy_points: f32,
+}
+
+/// A line of the `ndjson` format, tagged by `type` as the records of the stats.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record<'a> {
+    TeamBreakdown {
+        date: &'a str,
+        range: &'a str,
+        #[serde(flatten)]
+        breakdown: &'a TeamBreakdown,
+    },
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let mut dates = utils::date_strs(&config.date, &config.range);
+    dates.sort();
+    let players_per_date: Vec<_> = dates
+        .iter()
+        .map(|d| stats::players_for_date(d.clone(), &config))
+        .collect();
+    let per_date = players_per_date
+        .iter()
+        .map(|ps| stats::create_fantasy_players(ps, &s, &r, false))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let breakdowns = breakdowns(&per_date, &s, &r);
+    if json::is_json(&config.format) {
+        return print_json(&breakdowns, &config);
+    }
+
+    let f = format::formatter(&config.format)?;
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Fantasy Team Breakdown ({} ~ {})",
+                dates.first().unwrap(),
+                dates.last().unwrap()
+            )
+        )
+    );
+    print_breakdowns(&breakdowns, f.as_ref());
+
+    Ok(())
+}
+
+/// Prints the breakdowns as a document, or a `team_breakdown` record per team with `-f ndjson`.
+fn print_json(breakdowns: &[TeamBreakdown], config: &stats::Config) -> Result<(), Box<dyn Error>> {
+    if config.format != "ndjson" {
+        println!("{}", serde_json::to_string_pretty(breakdowns)?);
+        return Ok(());
+    }
+    for breakdown in breakdowns.iter() {
+        let record = Record::TeamBreakdown {
+            date: &config.date,
+            range: &config.range,
+            breakdown,
+        };
+        println!("{}", serde_json::to_string(&record)?);
+    }
+    Ok(())
+}
+
+/// Sums the fantasy points of the rostered players by team, player and category,
+/// sorted by the total points of the team.
+fn breakdowns(
+    per_date: &[Vec<FantasyPlayer>],
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> Vec<TeamBreakdown> {
+    let mut teams: Vec<TeamBreakdown> = vec![];
+    r.players.iter().for_each(|p| {
+        if !teams.iter().any(|t| t.team == p.team.as_ref().as_ref()) {
+            teams.push(TeamBreakdown {
+                team: p.team.to_string(),
+                ..Default::default()
+            });
+        }
+    });
+
+    for fp in per_date.iter().flatten() {
+        let team = match teams
+            .iter_mut()
+            .find(|t| t.team == fp.team.as_ref().as_ref())
+        {
+            Some(t) => t,
+            None => continue,
+        };
+        let pitcher = fp.player.pitcher_stats.is_some();
+
+        team.total += fp.fantasy_points;
+        if pitcher {
+            team.pitching += fp.fantasy_points;
+        } else {
+            team.batting += fp.fantasy_points;
+        }
+
+        match team
+            .players
+            .iter_mut()
+            .find(|c| c.name == fp.player.name && c.pitcher == pitcher)
+        {
+            Some(c) => {
+                c.games += 1;
+                c.fantasy_points += fp.fantasy_points;
+            }
+            None => team.players.push(Contribution {
+                name: fp.player.name.to_string(),
+                position: fp.player.primary_position.to_string(),
+                pitcher,
+                games: 1,
+                fantasy_points: fp.fantasy_points,
+                share: 0.0,
+            }),
+        }
+
+        stats::get_points_by_category(&fp.player, s)
+            .into_iter()
+            .filter(|c| c.multiplier != 0.0)
+            .for_each(|c| {
+                match team
+                    .categories
+                    .iter_mut()
+                    .find(|t| t.category == c.category)
+                {
+                    Some(t) if t.category == "P.IP" => {
+                        t.stat = add_innings(t.stat, c.stat);
+                        t.fantasy_points += c.points;
+                    }
+                    Some(t) => {
+                        t.stat += c.stat;
+                        t.fantasy_points += c.points;
+                    }
+                    None => team.categories.push(CategoryTotal {
+                        category: c.category.to_string(),
+                        stat: c.stat,
+                        fantasy_points: c.points,
+                    }),
+                }
+            });
+    }
+
+    teams.iter_mut().for_each(|t| {
+        let total = t.total;
+        t.players.iter_mut().for_each(|c| {
+            c.share = if total != 0.0 {
+                c.fantasy_points / total * 100.0
+            } else {
+                0.0
+            };
+        });
+        t.players
+            .sort_by(|a, b| b.fantasy_points.partial_cmp(&a.fantasy_points).unwrap());
+        // batting categories first, then pitching categories, in the order of points
+        t.categories.sort_by(|a, b| {
+            a.category[..2].cmp(&b.category[..2]).then(
+                b.fantasy_points
+                    .abs()
+                    .partial_cmp(&a.fantasy_points.abs())
+                    .unwrap(),
+            )
+        });
+    });
+    teams.sort_by(|a, b| b.total.partial_cmp(&a.total).unwrap());
+    teams
+}
+
+fn print_breakdowns(breakdowns: &[TeamBreakdown], f: &dyn Formatter) {
+    let columns = [
+        Column::new("Team", 20),
+        Column::new("Total", 9).right(),
+        Column::new("Batting", 9).right(),
+        Column::new("Pitching", 9).right(),
+    ];
+    let rows: Vec<Vec<String>> = breakdowns
+        .iter()
+        .map(|t| {
+            vec![
+                t.team.clone(),
+                format!("{:.2}", t.total),
+                format!("{:.2}", t.batting),
+                format!("{:.2}", t.pitching),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+
+    println!();
+    println!("{}", f.heading(2, "Players"));
+    let columns = [
+        Column::new("Team", 20),
+        Column::new("Player", 24),
+        Column::new("Pos", 5),
+        Column::new("G", 4).right(),
+        Column::new("FanPts", 9).right().points(),
+        Column::new("Share%", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = breakdowns
+        .iter()
+        .flat_map(|t| {
+            t.players.iter().map(move |c| {
+                vec![
+                    t.team.clone(),
+                    c.name.clone(),
+                    c.position.clone(),
+                    c.games.to_string(),
+                    format!("{:.2}", c.fantasy_points),
+                    format!("{:.1}", c.share),
+                ]
+            })
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+
+    println!();
+    println!("{}", f.heading(2, "Categories"));
+    let columns = [
+        Column::new("Team", 20),
+        Column::new("Category", 10),
+        Column::new("Stat", 9).right(),
+        Column::new("FanPts", 9).right().points(),
+    ];
+    let rows: Vec<Vec<String>> = breakdowns
+        .iter()
+        .flat_map(|t| {
+            t.categories.iter().map(move |c| {
+                vec![
+                    t.team.clone(),
+                    c.category.clone(),
+                    format_stat(&c.category, c.stat),
+                    format!("{:.2}", c.fantasy_points),
+                ]
+            })
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+/// Adds innings pitched in the notation of outs as tenths, e.g. 6.1 + 5.2 = 12.0.
+fn add_innings(a: f32, b: f32) -> f32 {
+    let outs = |ip: f32| ip.trunc() as u32 * 3 + (ip.fract() * 10.0).round() as u32;
+    let sum = outs(a) + outs(b);
+    (sum / 3) as f32 + (sum % 3) as f32 / 10.0
+}
+
+fn format_stat(category: &str, stat: f32) -> String {
+    if category == "P.IP" {
+        format!("{:.1}", stat)
+    } else {
+        format!("{}", stat.round() as i64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, Player};
+    use crate::utils::assert_eq_f32;
+
+    #[test]
+    fn breakdowns_should_sum_points_by_team_player_and_category() {
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+        let batter = Player::new_batter(
+            "Cody Bellinger",
+            "OF",
+            "RF",
+            BatterStats {
+                at_bats: 4,
+                runs: 1,
+                hits: 1,
+                home_runs: 1,
+                runs_batted_in: 2,
+                ..Default::default()
+            },
+        );
+        let per_date = vec![
+            vec![stats::to_fantasy_player(&batter, &s, &r)],
+            vec![stats::to_fantasy_player(&batter, &s, &r)],
+        ];
+        let points = per_date[0][0].fantasy_points;
+
+        let breakdowns = breakdowns(&per_date, &s, &r);
+        let bulls = breakdowns.iter().find(|t| t.team == "LA Bulls").unwrap();
+        assert_eq_f32(points * 2.0, bulls.total);
+        assert_eq_f32(points * 2.0, bulls.batting);
+        assert_eq_f32(0.0, bulls.pitching);
+        assert_eq!(1, bulls.players.len());
+        assert_eq!(2, bulls.players[0].games);
+        assert_eq_f32(100.0, bulls.players[0].share);
+        assert_eq_f32(
+            bulls.total,
+            bulls.categories.iter().map(|c| c.fantasy_points).sum(),
+        );
+        let hr = bulls
+            .categories
+            .iter()
+            .find(|c| c.category == "B.HR")
+            .unwrap();
+        assert_eq_f32(2.0, hr.stat);
+    }
+
+    #[test]
+    fn team_breakdown_record_should_be_tagged_by_type() {
+        let breakdown = TeamBreakdown {
+            team: "LA Bulls".to_string(),
+            total: 10.5,
+            ..Default::default()
+        };
+        let record = Record::TeamBreakdown {
+            date: "2019-08-25",
+            range: "1w",
+            breakdown: &breakdown,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+        assert_eq!("team_breakdown", json["type"]);
+        assert_eq!("2019-08-25", json["date"]);
+        assert_eq!("LA Bulls", json["team"]);
+        assert_eq!(10.5, json["total"]);
+        assert!(json["players"].is_array());
+    }
+
+    #[test]
+    fn add_innings_should_carry_thirds() {
+        assert_eq_f32(12.0, add_innings(6.1, 5.2));
+        assert_eq_f32(11.2, add_innings(6.0, 5.2));
+        assert_eq_f32(1.1, add_innings(0.2, 0.2));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::stats::consistency::{self, Consistency};
+use crate::stats::filter::{self, Expr};
+use crate::stats::output;
+use crate::stats::FantasyPlayer;
+
+use clap::ArgMatches;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct UnknownColumn(String);
+impl fmt::Display for UnknownColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown column {}, available columns: {}",
+            self.0,
+            output::HEADERS.join(", ")
+        )
+    }
+}
+impl Error for UnknownColumn {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidSortOrder(String);
+impl fmt::Display for InvalidSortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid sort order {}, should be asc or desc", self.0)
+    }
+}
+impl Error for InvalidSortOrder {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Only(&'static str),
+    Add(&'static str),
+    Remove(&'static str),
+}
+
+/// Columns of the player tables, the sort key and the filter of the rows, by `--columns`,
+/// `--sort` and `--where`.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    changes: Vec<Change>,
+    sort: Option<(&'static str, bool)>,
+    filter: Option<Expr>,
+}
+impl Options {
+    pub fn from_matches(matches: &ArgMatches) -> Result<Options, Box<dyn Error>> {
+        Ok(Options {
+            changes: match matches.value_of("columns") {
+                Some(s) => parse_columns(s)?,
+                None => vec![],
+            },
+            sort: match matches.value_of("sort") {
+                Some(s) => Some(parse_sort(s)?),
+                None => None,
+            },
+            filter: match matches.value_of("where") {
+                Some(s) => Some(filter::parse(s)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Returns true if the player matches the `--where` expression, or there is none.
+    pub fn keep(&self, fp: &FantasyPlayer) -> bool {
+        match &self.filter {
+            Some(f) => f.matches(fp),
+            None => true,
+        }
+    }
+
+    /// Returns the default headers with the changes applied. Names without a sign replace the defaults.
+    pub fn headers(&self, defaults: Vec<String>) -> Vec<String> {
+        let only: Vec<String> = self
+            .changes
+            .iter()
+            .filter_map(|c| match c {
+                Change::Only(h) => Some(h.to_string()),
+                _ => None,
+            })
+            .collect();
+        let mut headers = if only.is_empty() { defaults } else { only };
+
+        self.changes.iter().for_each(|c| match c {
+            Change::Add(h) if !headers.iter().any(|x| x == h) => headers.push(h.to_string()),
+            Change::Remove(h) => headers.retain(|x| x != h),
+            _ => (),
+        });
+        headers
+    }
+
+    /// Returns true if the players are sorted by a consistency column, e.g. `Mean`.
+    pub fn sorts_by_consistency(&self) -> bool {
+        match self.sort {
+            Some((header, _)) => consistency::COLUMNS.contains(&header),
+            None => false,
+        }
+    }
+
+    /// Sorts the players by the `--sort` column, keeping the order of ties. Players without
+    /// the stat, e.g. pitchers for a batting stat or players without consistency, are put last
+    /// in both orders.
+    pub fn sort(&self, players: &mut [FantasyPlayer], consistency: &HashMap<String, Consistency>) {
+        let (header, desc) = match self.sort {
+            Some(sort) => sort,
+            None => return,
+        };
+        let value = |fp: &FantasyPlayer| {
+            if !consistency::COLUMNS.contains(&header) {
+                return output::get_stat_value(fp, header);
+            }
+            consistency
+                .get(&fp.key())
+                .map(|c| c.value(header).to_string())
+                .unwrap_or_default()
+        };
+        players.sort_by(|a, b| {
+            let (a, b) = (value(a), value(b));
+            match (a.is_empty(), b.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    let ord = compare_cells(&a, &b);
+                    if desc {
+                        ord.reverse()
+                    } else {
+                        ord
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f32>(), b.parse::<f32>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// Returns the header of given name, case-insensitively.
+fn header(name: &str) -> Result<&'static str, UnknownColumn> {
+    output::HEADERS
+        .iter()
+        .find(|h| h.eq_ignore_ascii_case(name.trim()))
+        .cloned()
+        .ok_or_else(|| UnknownColumn(name.trim().to_string()))
+}
+
+fn parse_columns(s: &str) -> Result<Vec<Change>, UnknownColumn> {
+    s.split(',')
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| {
+            let c = c.trim();
+            if let Some(name) = c.strip_prefix('+') {
+                header(name).map(Change::Add)
+            } else if let Some(name) = c.strip_prefix('-') {
+                header(name).map(Change::Remove)
+            } else {
+                header(c).map(Change::Only)
+            }
+        })
+        .collect()
+}
+
+/// Parses `COLUMN[:asc|desc]`, where the column is of the player tables or consistency.
+/// Player, Team and Pos are ascending and the others are descending by default.
+fn parse_sort(s: &str) -> Result<(&'static str, bool), Box<dyn Error>> {
+    let mut arr = s.splitn(2, ':');
+    let name = arr.next().unwrap_or("");
+    let h = match consistency::COLUMNS
+        .iter()
+        .find(|c| c.eq_ignore_ascii_case(name.trim()))
+    {
+        Some(c) => c,
+        None => header(name)?,
+    };
+    let desc = match arr.next().map(str::trim) {
+        None => !["Player", "Team", "Pos"].contains(&h),
+        Some(o) if o.eq_ignore_ascii_case("asc") => false,
+        Some(o) if o.eq_ignore_ascii_case("desc") => true,
+        Some(o) => return Err(Box::new(InvalidSortOrder(o.to_string()))),
+    };
+    Ok((h, desc))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        ["Player", "Team", "FanPts", "Pos", "B.HR", "B.SB", "P.K"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn headers_should_add_remove_or_replace_columns() {
+        let options = Options {
+            changes: parse_columns("+b.ab, -B.SB,+B.HR").unwrap(),
+            sort: None,
+            filter: None,
+        };
+        assert_eq!(
+            vec!["Player", "Team", "FanPts", "Pos", "B.HR", "P.K", "B.AB"],
+            options.headers(defaults())
+        );
+
+        let options = Options {
+            changes: parse_columns("Player,FanPts,P.ER,-FanPts").unwrap(),
+            sort: None,
+            filter: None,
+        };
+        assert_eq!(vec!["Player", "P.ER"], options.headers(defaults()));
+
+        assert!(parse_columns("+B.XYZ").is_err());
+    }
+
+    #[test]
+    fn parse_sort_should_return_header_and_order() {
+        assert_eq!(("B.HR", true), parse_sort("b.hr").unwrap());
+        assert_eq!(("B.HR", false), parse_sort("B.HR:asc").unwrap());
+        assert_eq!(("Player", false), parse_sort("player").unwrap());
+        assert_eq!(("Team", true), parse_sort("Team:DESC").unwrap());
+        assert!(parse_sort("B.HR:up").is_err());
+        assert!(parse_sort("HR").is_err());
+        assert_eq!(("SD", true), parse_sort("sd").unwrap());
+        assert_eq!(("Boom%", false), parse_sort("boom%:asc").unwrap());
+    }
+
+    #[test]
+    fn sort_should_sort_by_consistency_columns() {
+        use crate::stats::{BatterStats, Player};
+        use std::borrow::Cow;
+        use std::rc::Rc;
+
+        let fp = |name: &'static str| FantasyPlayer {
+            team: Rc::new(Cow::Borrowed("LA Bulls")),
+            player: Player::new_batter(name, "OF", "CF", BatterStats::default()),
+            fantasy_points: 0.0,
+        };
+        let mut players = vec![fp("A"), fp("B"), fp("C")];
+        let consistency: HashMap<String, Consistency> = vec![
+            (players[0].key(), Consistency::new(&[1.0, 3.0], 10.0, 0.0)),
+            (players[1].key(), Consistency::new(&[2.0, 8.0], 10.0, 0.0)),
+        ]
+        .into_iter()
+        .collect();
+        let options = Options {
+            changes: vec![],
+            sort: Some(parse_sort("mean").unwrap()),
+            filter: None,
+        };
+        assert!(options.sorts_by_consistency());
+
+        options.sort(&mut players, &consistency);
+        let names: Vec<_> = players.iter().map(|p| p.player.name.to_string()).collect();
+        assert_eq!(vec!["B", "A", "C"], names);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, output, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+
+const SPLITS: [(&str, &str); 3] = [("7d", "1w"), ("14d", "2w"), ("30d", "1m")];
+
+#[derive(Debug, Clone)]
+struct NoStatsFound(String);
+impl fmt::Display for NoStatsFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no stats found for player {}", self.0)
+    }
+}
+impl Error for NoStatsFound {}
+
+/// Fantasy players of given names for each date.
+type PerDate<'a> = Vec<(String, Vec<FantasyPlayer<'a>>)>;
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let names: Vec<String> = matches
+        .values_of("names")
+        .unwrap()
+        .map(|n| n.to_lowercase())
+        .collect();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let dates: BTreeSet<String> = SPLITS
+        .iter()
+        .map(|(_, split)| *split)
+        .chain(vec![range])
+        .flat_map(|rg| utils::date_strs(&config.date, rg))
+        .collect();
+
+    let players: Vec<_> = dates
+        .iter()
+        .map(|d| (d.clone(), stats::players_for_date(d.clone(), &config)))
+        .collect();
+    let per_date: PerDate = players
+        .iter()
+        .map(|(d, ps)| {
+            let fps = ps
+                .iter()
+                .filter(|p| names.contains(&p.name.to_lowercase()))
+                .map(|p| stats::to_fantasy_player(p, &s, &r))
+                .collect();
+            (d.clone(), fps)
+        })
+        .collect();
+
+    let columns = columns(&per_date, &names)?;
+
+    let main_dates = utils::date_strs(&config.date, range);
+    let main: Vec<_> = columns
+        .iter()
+        .map(|c| aggregate(&per_date, c, &main_dates))
+        .collect();
+    let splits: Vec<Vec<_>> = SPLITS
+        .iter()
+        .map(|(_, split)| {
+            let dates = utils::date_strs(&config.date, split);
+            columns
+                .iter()
+                .map(|c| aggregate(&per_date, c, &dates))
+                .collect()
+        })
+        .collect();
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!("Player Comparison ({}, until {})", range, config.date)
+        )
+    );
+    print_comparison(&columns, &main, &splits, &s, f.as_ref());
+
+    Ok(())
+}
+
+/// Returns (name, is pitcher) of the players in the order of given names.
+/// A two-way player has two columns, for batting and pitching.
+fn columns(per_date: &PerDate, names: &[String]) -> Result<Vec<(String, bool)>, Box<dyn Error>> {
+    let mut columns = vec![];
+    for name in names.iter() {
+        let mut found: Vec<(String, bool)> = vec![];
+        per_date
+            .iter()
+            .flat_map(|(_, fps)| fps.iter())
+            .for_each(|fp| {
+                let column = (
+                    fp.player.name.to_string(),
+                    fp.player.pitcher_stats.is_some(),
+                );
+                if fp.player.name.to_lowercase() == *name && !found.contains(&column) {
+                    found.push(column);
+                }
+            });
+        if found.is_empty() {
+            return Err(Box::new(NoStatsFound(name.to_string())));
+        }
+        found.sort_by_key(|(_, pitcher)| *pitcher);
+        columns.append(&mut found);
+    }
+    Ok(columns)
+}
+
+/// Sums the stats of the player over given dates, returning the player and the number of games.
+fn aggregate<'a>(
+    per_date: &PerDate<'a>,
+    column: &(String, bool),
+    dates: &[String],
+) -> (Option<FantasyPlayer<'a>>, usize) {
+    per_date
+        .iter()
+        .filter(|(d, _)| dates.contains(d))
+        .flat_map(|(_, fps)| fps.iter())
+        .filter(|fp| fp.player.name == column.0 && fp.player.pitcher_stats.is_some() == column.1)
+        .fold((None, 0), |(acc, games), fp| match acc {
+            None => (Some(fp.clone()), games + 1),
+            Some(mut total) => {
+                total.add_stats(fp.clone());
+                (Some(total), games + 1)
+            }
+        })
+}
+
+fn per_game(points: f32, games: usize) -> f32 {
+    if games == 0 {
+        return 0.0;
+    }
+    points / games as f32
+}
+
+fn print_comparison(
+    columns: &[(String, bool)],
+    main: &[(Option<FantasyPlayer>, usize)],
+    splits: &[Vec<(Option<FantasyPlayer>, usize)>],
+    s: &scoring::ScoringRule,
+    f: &dyn Formatter,
+) {
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut print_row = |label: &str, cells: Vec<String>| {
+        rows.push(std::iter::once(label.to_string()).chain(cells).collect());
+    };
+    let cells = |cell: &dyn Fn(&FantasyPlayer, usize) -> String| {
+        main.iter()
+            .map(|(fp, games)| match fp {
+                Some(fp) => cell(fp, *games),
+                None => "".to_string(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    print_row("Team", cells(&|fp, _| fp.team.to_string()));
+    print_row("MLB", cells(&|fp, _| fp.player.mlb_team.to_string()));
+    print_row(
+        "Pos",
+        cells(&|fp, _| fp.player.primary_position.to_string()),
+    );
+    print_row("G", cells(&|_, games| games.to_string()));
+    print_row(
+        "FanPts",
+        cells(&|fp, _| format!("{:.2}", fp.fantasy_points)),
+    );
+    print_row(
+        "Pts/G",
+        cells(&|fp, games| format!("{:.2}", per_game(fp.fantasy_points, games))),
+    );
+
+    let mut headers: Vec<String> = vec![];
+    if main
+        .iter()
+        .any(|(fp, _)| matches!(fp, Some(fp) if fp.player.batter_stats.is_some()))
+    {
+        headers.append(&mut s.get_header_items_for_batter());
+    }
+    if main
+        .iter()
+        .any(|(fp, _)| matches!(fp, Some(fp) if fp.player.pitcher_stats.is_some()))
+    {
+        headers.append(&mut s.get_header_items_for_pitcher());
+    }
+    let headers: Vec<String> = headers
+        .into_iter()
+        .filter(|h| h.starts_with("B.") || h.starts_with("P."))
+        .collect();
+    let stats: Vec<Vec<String>> = main
+        .iter()
+        .map(|(fp, _)| match fp {
+            Some(fp) => output::get_stat_cells(fp, &headers),
+            None => vec!["".to_string(); headers.len()],
+        })
+        .collect();
+    headers.iter().enumerate().for_each(|(i, h)| {
+        print_row(h, stats.iter().map(|c| c[i].clone()).collect());
+    });
+
+    SPLITS
+        .iter()
+        .zip(splits.iter())
+        .for_each(|((label, _), split)| {
+            let split_cells = |cell: &dyn Fn(f32, usize) -> String| {
+                split
+                    .iter()
+                    .map(|(fp, games)| match fp {
+                        Some(fp) => cell(fp.fantasy_points, *games),
+                        None => cell(0.0, 0),
+                    })
+                    .collect::<Vec<_>>()
+            };
+            print_row(
+                &format!("{} G", label),
+                split_cells(&|_, games| games.to_string()),
+            );
+            print_row(
+                &format!("{} FanPts", label),
+                split_cells(&|pts, _| format!("{:.2}", pts)),
+            );
+            print_row(
+                &format!("{} Pts/G", label),
+                split_cells(&|pts, games| format!("{:.2}", per_game(pts, games))),
+            );
+        });
+
+    let columns: Vec<Column> = std::iter::once(Column::new("Player", 14))
+        .chain(
+            columns
+                .iter()
+                .map(|(name, _)| Column::new(name, 18).right().truncated()),
+        )
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, PitcherStats, Player};
+
+    #[test]
+    fn aggregate_should_sum_stats_over_dates() {
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+        let batter = Player::new_batter(
+            "Shohei Ohtani",
+            "DH",
+            "DH",
+            BatterStats {
+                at_bats: 4,
+                hits: 2,
+                singles: 2,
+                ..Default::default()
+            },
+        );
+        let pitcher = Player::new_pitcher(
+            "Shohei Ohtani",
+            "P",
+            "SP",
+            PitcherStats {
+                strikeouts: 8,
+                ..Default::default()
+            },
+        );
+        let per_date: PerDate = vec![
+            (
+                "2019-07-01".to_string(),
+                vec![
+                    stats::to_fantasy_player(&batter, &s, &r),
+                    stats::to_fantasy_player(&pitcher, &s, &r),
+                ],
+            ),
+            (
+                "2019-07-02".to_string(),
+                vec![stats::to_fantasy_player(&batter, &s, &r)],
+            ),
+        ];
+
+        let columns = columns(&per_date, &["shohei ohtani".to_string()]).unwrap();
+        assert_eq!(
+            vec![
+                ("Shohei Ohtani".to_string(), false),
+                ("Shohei Ohtani".to_string(), true)
+            ],
+            columns
+        );
+        assert!(super::columns(&per_date, &["mike trout".to_string()]).is_err());
+
+        let dates = vec!["2019-07-01".to_string(), "2019-07-02".to_string()];
+        let (fp, games) = aggregate(&per_date, &columns[0], &dates);
+        assert_eq!(2, games);
+        assert_eq!(8, fp.unwrap().player.batter_stats.unwrap().at_bats);
+
+        let (fp, games) = aggregate(&per_date, &columns[1], &dates[1..]);
+        assert_eq!(0, games);
+        assert!(fp.is_none());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::stats::format::Column;
+use crate::stats::FantasyPlayer;
+
+use clap::ArgMatches;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+pub const COLUMNS: [&str; 8] = ["G", "Mean", "Median", "SD", "P10", "P90", "Boom%", "Bust%"];
+
+#[derive(Debug, Clone)]
+pub struct InvalidThreshold(String);
+impl fmt::Display for InvalidThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid threshold {}, should be BATTER_PTS:PITCHER_PTS",
+            self.0
+        )
+    }
+}
+impl Error for InvalidThreshold {}
+
+/// Boom/bust thresholds of daily fantasy points as (batter, pitcher).
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub boom: (f32, f32),
+    pub bust: (f32, f32),
+}
+impl Options {
+    /// Returns None if neither consistency columns nor sorting by them are requested.
+    pub fn from_matches(
+        matches: &ArgMatches,
+        sort_by_consistency: bool,
+    ) -> Result<Option<Options>, Box<dyn Error>> {
+        if matches.occurrences_of("consistency") == 0 && !sort_by_consistency {
+            return Ok(None);
+        }
+
+        Ok(Some(Options {
+            boom: parse_points(matches.value_of("boom").unwrap())?,
+            bust: parse_points(matches.value_of("bust").unwrap())?,
+        }))
+    }
+}
+
+fn parse_points(s: &str) -> Result<(f32, f32), InvalidThreshold> {
+    let invalid = || InvalidThreshold(s.to_string());
+    let arr: Vec<&str> = s.split(':').collect();
+    if arr.len() != 2 {
+        return Err(invalid());
+    }
+    match (arr[0].parse::<f32>(), arr[1].parse::<f32>()) {
+        (Ok(b), Ok(p)) => Ok((b, p)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Distribution of the daily fantasy points of a player. Games of a doubleheader
+/// are counted as a game.
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct Consistency {
+    pub games: usize,
+    pub mean: f32,
+    pub median: f32,
+    pub std_dev: f32,
+    pub p10: f32,
+    pub p90: f32,
+    pub boom: f32,
+    pub bust: f32,
+}
+impl Consistency {
+    pub fn new(points: &[f32], boom: f32, bust: f32) -> Consistency {
+        if points.is_empty() {
+            return Consistency::default();
+        }
+
+        let mut sorted = points.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len() as f32;
+        let mean = sorted.iter().sum::<f32>() / n;
+        let variance = sorted.iter().map(|p| (p - mean).powi(2)).sum::<f32>() / n;
+
+        Consistency {
+            games: sorted.len(),
+            mean,
+            median: percentile(&sorted, 50.0),
+            std_dev: variance.sqrt(),
+            p10: percentile(&sorted, 10.0),
+            p90: percentile(&sorted, 90.0),
+            boom: sorted.iter().filter(|p| **p >= boom).count() as f32 / n * 100.0,
+            bust: sorted.iter().filter(|p| **p <= bust).count() as f32 / n * 100.0,
+        }
+    }
+
+    /// Returns the value of a consistency column.
+    pub fn value(&self, column: &str) -> f32 {
+        match column {
+            "G" => self.games as f32,
+            "Mean" => self.mean,
+            "Median" => self.median,
+            "SD" => self.std_dev,
+            "P10" => self.p10,
+            "P90" => self.p90,
+            "Boom%" => self.boom,
+            "Bust%" => self.bust,
+            _ => 0.0,
+        }
+    }
+
+    pub fn columns() -> Vec<Column> {
+        COLUMNS
+            .iter()
+            .map(|name| Column::new(name, 7).right())
+            .collect()
+    }
+
+    pub fn cells(&self) -> Vec<String> {
+        vec![
+            self.games.to_string(),
+            format!("{:.2}", self.mean),
+            format!("{:.2}", self.median),
+            format!("{:.2}", self.std_dev),
+            format!("{:.2}", self.p10),
+            format!("{:.2}", self.p90),
+            format!("{:.1}", self.boom),
+            format!("{:.1}", self.bust),
+        ]
+    }
+}
+
+/// Linear interpolation between the closest ranks of sorted values.
+fn percentile(sorted: &[f32], pct: f32) -> f32 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let rank = pct / 100.0 * (sorted.len() - 1) as f32;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f32)
+}
+
+/// Computes the consistency of each player from the fantasy players of each date.
+pub fn consistency_per_player(
+    per_date: &[Vec<FantasyPlayer>],
+    options: &Options,
+) -> HashMap<String, Consistency> {
+    let mut points: HashMap<String, (bool, Vec<f32>)> = HashMap::new();
+    per_date.iter().flat_map(|fps| fps.iter()).for_each(|fp| {
+        points
+            .entry(fp.key())
+            .or_insert((fp.player.pitcher_stats.is_some(), vec![]))
+            .1
+            .push(fp.fantasy_points);
+    });
+
+    points
+        .into_iter()
+        .map(|(k, (pitcher, pts))| {
+            let (boom, bust) = if pitcher {
+                (options.boom.1, options.bust.1)
+            } else {
+                (options.boom.0, options.bust.0)
+            };
+            (k, Consistency::new(&pts, boom, bust))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::assert_eq_f32;
+
+    #[test]
+    fn new_should_compute_distribution_of_points() {
+        let points = [7.0, 2.0, 10.0, 0.0, 5.0, 1.0, 9.0, 4.0, 8.0, 3.0, 6.0];
+        let c = Consistency::new(&points, 8.0, 1.0);
+
+        assert_eq!(11, c.games);
+        assert_eq_f32(5.0, c.mean);
+        assert_eq_f32(5.0, c.median);
+        assert_eq_f32(3.1623, c.std_dev);
+        assert_eq_f32(1.0, c.p10);
+        assert_eq_f32(9.0, c.p90);
+        assert_eq_f32(27.2727, c.boom);
+        assert_eq_f32(18.1818, c.bust);
+
+        let c = Consistency::new(&[3.0, 5.0], 8.0, 1.0);
+        assert_eq_f32(4.0, c.median);
+        assert_eq_f32(0.0, c.boom);
+    }
+
+    #[test]
+    fn parse_points_should_return_batter_and_pitcher_points() {
+        assert_eq!((10.0, 20.5), parse_points("10:20.5").unwrap());
+        assert!(parse_points("10").is_err());
+        assert!(parse_points("a:b").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column};
+use crate::stats::{self, gamelog, CategoryPoints};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct PlayerNotFound(String, String, String);
+impl fmt::Display for PlayerNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no stats found for player {} from {} to {}",
+            self.0, self.1, self.2
+        )
+    }
+}
+impl Error for PlayerNotFound {}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let name = matches.value_of("name").unwrap();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let mut dates = utils::date_strs(&config.date, &config.range);
+    dates.sort();
+
+    let players: Vec<_> = dates
+        .iter()
+        .map(|d| (d.clone(), stats::players_for_date(d.clone(), &config)))
+        .collect();
+    let (batting, pitching) = gamelog::game_logs(&players, name, &s, &r);
+    if batting.is_empty() && pitching.is_empty() {
+        return Err(Box::new(PlayerNotFound(
+            name.to_string(),
+            dates.first().unwrap().clone(),
+            dates.last().unwrap().clone(),
+        )));
+    }
+
+    let fp = batting.first().unwrap_or_else(|| &pitching[0]);
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Fantasy Points Explained: {} ({}, {} ~ {})",
+                fp.1.player.name,
+                fp.1.team,
+                dates.first().unwrap(),
+                dates.last().unwrap()
+            )
+        )
+    );
+
+    let mut rows = vec![];
+    let mut total = 0.0;
+    let mut games = 0;
+    for (date, fp) in batting.iter().chain(pitching.iter()) {
+        let categories = stats::get_points_by_category(&fp.player, &s);
+        let points = stats::get_fantasy_points(&fp.player, &s);
+        rows.extend(game_rows(date, &fp.player.opponent, &categories, points));
+        total += points;
+        games += 1;
+    }
+    rows.push(vec![
+        "Total".to_string(),
+        "".to_string(),
+        "".to_string(),
+        "".to_string(),
+        "".to_string(),
+        format!("{:.2}", total),
+        format!("{} game(s)", games),
+    ]);
+
+    let columns = [
+        Column::new("Date", 12),
+        Column::new("Opp", 6),
+        Column::new("Category", 10),
+        Column::new("Stat", 8).right(),
+        Column::new("Mult", 8).right(),
+        Column::new("FanPts", 9).right().points(),
+        Column::new("Note", 5),
+    ];
+    println!("{}", f.table(&columns, &rows));
+
+    Ok(())
+}
+
+/// Returns the rows of the non-zero stats of a game with their multipliers and points,
+/// and the row of the points of the game, i.e. the sum of the points of all categories.
+fn game_rows(
+    date: &str,
+    opponent: &str,
+    categories: &[CategoryPoints],
+    points: f32,
+) -> Vec<Vec<String>> {
+    let opponent = if opponent.is_empty() { "-" } else { opponent };
+    let mut rows: Vec<Vec<String>> = categories
+        .iter()
+        .filter(|c| c.stat != 0.0)
+        .map(|c| {
+            let stat = if c.category == "P.IP" {
+                format!("{:.1}", c.stat)
+            } else {
+                format!("{}", c.stat)
+            };
+            vec![
+                date.to_string(),
+                opponent.to_string(),
+                c.category.to_string(),
+                stat,
+                format!("{:.2}", c.multiplier),
+                format!("{:.2}", c.points),
+                note(c),
+            ]
+        })
+        .collect();
+    rows.push(vec![
+        date.to_string(),
+        opponent.to_string(),
+        "Game".to_string(),
+        "".to_string(),
+        "".to_string(),
+        format!("{:.2}", points),
+        "".to_string(),
+    ]);
+    rows
+}
+
+/// Explains the partial innings, e.g. "6 x 3 + 1/3 x 3" for 6.1 innings.
+fn note(c: &CategoryPoints) -> String {
+    if c.category != "P.IP" {
+        return "".to_string();
+    }
+    let innings = ((c.stat * 10.0) as i32 / 10) as f32;
+    let thirds = match (c.stat * 10.0 % 10.0) as i32 {
+        1 => " + 1/3",
+        2 => " + 2/3",
+        _ => return format!("{} x {}", innings, c.multiplier),
+    };
+    format!(
+        "{} x {}{} x {}",
+        innings, c.multiplier, thirds, c.multiplier
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn note_should_explain_partial_innings() {
+        let ip = |stat: f32| CategoryPoints {
+            category: "P.IP",
+            stat,
+            multiplier: 3.0,
+            points: 0.0,
+        };
+        assert_eq!("6 x 3 + 1/3 x 3", note(&ip(6.1)));
+        assert_eq!("5 x 3 + 2/3 x 3", note(&ip(5.2)));
+        assert_eq!("7 x 3", note(&ip(7.0)));
+        assert_eq!(
+            "",
+            note(&CategoryPoints {
+                category: "P.K",
+                stat: 10.0,
+                multiplier: 1.0,
+                points: 10.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use crate::league::{roster, scoring};
+use crate::stats::{self, BatterStats, PitcherStats};
+
+use arrow::array::{ArrayRef, BooleanArray, Date32Array, Float32Array, StringArray, UInt32Array};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+use chrono::NaiveDate;
+use clap::ArgMatches;
+use log::warn;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct SchemaMismatch(String);
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} has different columns (exported with or without --league?), use --force to rewrite all the dates",
+            self.0
+        )
+    }
+}
+impl Error for SchemaMismatch {}
+
+type BatterField = (&'static str, fn(&BatterStats) -> u32);
+type PitcherField = (&'static str, fn(&PitcherStats) -> u32);
+
+fn batter_fields() -> Vec<BatterField> {
+    vec![
+        ("b_at_bats", |s| s.at_bats),
+        ("b_runs", |s| s.runs),
+        ("b_hits", |s| s.hits),
+        ("b_singles", |s| s.singles),
+        ("b_doubles", |s| s.doubles),
+        ("b_triples", |s| s.triples),
+        ("b_home_runs", |s| s.home_runs),
+        ("b_runs_batted_in", |s| s.runs_batted_in),
+        ("b_sacrifice_hits", |s| s.sacrifice_hits),
+        ("b_stolen_bases", |s| s.stolen_bases),
+        ("b_caught_stealing", |s| s.caught_stealing),
+        ("b_walks", |s| s.walks),
+        ("b_intentional_walks", |s| s.intentional_walks),
+        ("b_hit_by_pitch", |s| s.hit_by_pitch),
+        ("b_strikeouts", |s| s.strikeouts),
+        ("b_ground_into_double_play", |s| s.ground_into_double_play),
+        ("b_total_bases", |s| s.total_bases),
+    ]
+}
+
+/// Pitching stats except `p_innings_pitched`, which is the only float.
+fn pitcher_fields() -> Vec<PitcherField> {
+    vec![
+        ("p_wins", |s| s.wins),
+        ("p_losses", |s| s.losses),
+        ("p_complete_games", |s| s.complete_games),
+        ("p_shutouts", |s| s.shutouts),
+        ("p_saves", |s| s.saves),
+        ("p_outs", |s| s.outs),
+        ("p_hits", |s| s.hits),
+        ("p_earned_runs", |s| s.earned_runs),
+        ("p_home_runs", |s| s.home_runs),
+        ("p_walks", |s| s.walks),
+        ("p_intentional_walks", |s| s.intentional_walks),
+        ("p_hit_batters", |s| s.hit_batters),
+        ("p_strikeouts", |s| s.strikeouts),
+        ("p_stolen_bases_allowed", |s| s.stolen_bases_allowed),
+        ("p_batters_grounded_into_double_plays", |s| {
+            s.batters_grounded_into_double_plays
+        }),
+        ("p_total_bases_allowed", |s| s.total_bases_allowed),
+    ]
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let dir = matches.value_of("output").unwrap();
+    let format = matches.value_of("format").unwrap();
+    let s = match matches.value_of("league") {
+        Some(league) => Some(scoring::load(&league.to_string())?),
+        None => None,
+    };
+
+    let (dates, rows) = export(
+        dir,
+        format,
+        &stats::cached_stats_files(),
+        s.as_ref(),
+        matches.is_present("force"),
+    )?;
+    if dates == 0 {
+        println!("{} is up to date with the stats cache", dir);
+    } else {
+        println!("{} dates ({} rows) exported to {}", dates, rows, dir);
+    }
+
+    Ok(())
+}
+
+/// Writes a partition file per cached date not exported yet, `<dir>/date=YYYY-MM-DD/part.parquet`,
+/// leaving the exported ones as they are. Returns the numbers of the written dates and rows.
+fn export(
+    dir: &str,
+    format: &str,
+    cached: &[(String, String)],
+    s: Option<&scoring::ScoringRule>,
+    force: bool,
+) -> Result<(usize, usize), Box<dyn Error>> {
+    let schema = Arc::new(schema(s.is_some()));
+
+    let mut dated = vec![];
+    for (date, filepath) in cached {
+        match days(date) {
+            Ok(day) => dated.push((day, date, filepath)),
+            Err(_) => warn!("Skipping {}, which is not named by a date", filepath),
+        }
+    }
+
+    if !force {
+        let exported = dated
+            .iter()
+            .map(|(_, date, _)| part_path(dir, date, format))
+            .find(|part| part.exists());
+        if let Some(part) = exported {
+            if read_schema(&part, format)?.fields() != schema.fields() {
+                return Err(Box::new(SchemaMismatch(dir.to_string())));
+            }
+        }
+    }
+
+    let (mut dates, mut rows) = (0, 0);
+    for (day, date, filepath) in dated {
+        let part = part_path(dir, date, format);
+        if !force && part.exists() {
+            continue;
+        }
+        let players = stats::get_players_from_file(filepath)?;
+        let batch = record_batch(&schema, day, &players, s)?;
+        write_part(&part, format, &schema, &batch)?;
+        dates += 1;
+        rows += batch.num_rows();
+    }
+
+    Ok((dates, rows))
+}
+
+fn schema(with_points: bool) -> Schema {
+    let mut fields = vec![
+        Field::new("date", DataType::Date32, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("mlb_team", DataType::Utf8, true),
+        Field::new("opponent", DataType::Utf8, true),
+        Field::new("home", DataType::Boolean, true),
+        Field::new("day_night", DataType::Utf8, true),
+        Field::new("venue", DataType::Utf8, true),
+        Field::new("position", DataType::Utf8, false),
+        Field::new("primary_position", DataType::Utf8, false),
+    ];
+    fields.extend(
+        batter_fields()
+            .iter()
+            .map(|(name, _)| Field::new(*name, DataType::UInt32, true)),
+    );
+    fields.push(Field::new("p_innings_pitched", DataType::Float32, true));
+    fields.extend(
+        pitcher_fields()
+            .iter()
+            .map(|(name, _)| Field::new(*name, DataType::UInt32, true)),
+    );
+    if with_points {
+        fields.push(Field::new("fantasy_points", DataType::Float32, false));
+    }
+    Schema::new(fields)
+}
+
+/// Returns the rows of the players of a date, merging the stats of doubleheaders so that
+/// there is a row per player per date. Stats of the other role, e.g. batting of a pitcher, are null.
+fn record_batch(
+    schema: &SchemaRef,
+    day: i32,
+    players: &[stats::Player],
+    s: Option<&scoring::ScoringRule>,
+) -> Result<RecordBatch, Box<dyn Error>> {
+    let default_rule = scoring::ScoringRule::default();
+    let all = roster::Roster::default();
+    let mut fps = stats::create_fantasy_players(players, s.unwrap_or(&default_rule), &all, true)?;
+    fps.sort_by(|a, b| {
+        (&a.player.name, a.player.pitcher_stats.is_some())
+            .cmp(&(&b.player.name, b.player.pitcher_stats.is_some()))
+    });
+
+    let text = |values: Vec<&str>| -> ArrayRef {
+        Arc::new(StringArray::from(
+            values
+                .into_iter()
+                .map(|v| Some(v).filter(|v| !v.is_empty()))
+                .collect::<Vec<_>>(),
+        ))
+    };
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(Date32Array::from(vec![day; fps.len()])),
+        Arc::new(StringArray::from(
+            fps.iter().map(|fp| &fp.player.name[..]).collect::<Vec<_>>(),
+        )),
+        text(fps.iter().map(|fp| &fp.player.mlb_team[..]).collect()),
+        text(fps.iter().map(|fp| &fp.player.opponent[..]).collect()),
+        Arc::new(BooleanArray::from(
+            fps.iter().map(|fp| fp.player.home).collect::<Vec<_>>(),
+        )),
+        text(fps.iter().map(|fp| &fp.player.day_night[..]).collect()),
+        text(fps.iter().map(|fp| &fp.player.venue[..]).collect()),
+        Arc::new(StringArray::from(
+            fps.iter()
+                .map(|fp| &fp.player.position[..])
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            fps.iter()
+                .map(|fp| &fp.player.primary_position[..])
+                .collect::<Vec<_>>(),
+        )),
+    ];
+    columns.extend(batter_fields().into_iter().map(|(_, f)| -> ArrayRef {
+        Arc::new(UInt32Array::from(
+            fps.iter()
+                .map(|fp| fp.player.batter_stats.as_ref().map(f))
+                .collect::<Vec<_>>(),
+        ))
+    }));
+    columns.push(Arc::new(Float32Array::from(
+        fps.iter()
+            .map(|fp| fp.player.pitcher_stats.as_ref().map(|s| s.innings_pitched))
+            .collect::<Vec<_>>(),
+    )));
+    columns.extend(pitcher_fields().into_iter().map(|(_, f)| -> ArrayRef {
+        Arc::new(UInt32Array::from(
+            fps.iter()
+                .map(|fp| fp.player.pitcher_stats.as_ref().map(f))
+                .collect::<Vec<_>>(),
+        ))
+    }));
+    if s.is_some() {
+        columns.push(Arc::new(Float32Array::from(
+            fps.iter().map(|fp| fp.fantasy_points).collect::<Vec<_>>(),
+        )));
+    }
+
+    Ok(RecordBatch::try_new(Arc::clone(schema), columns)?)
+}
+
+/// Returns the days since the epoch of a `YYYY-MM-DD` date, as in the date column.
+fn days(date: &str) -> Result<i32, Box<dyn Error>> {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    Ok((NaiveDate::parse_from_str(date, "%Y-%m-%d")? - epoch).num_days() as i32)
+}
+
+fn part_path(dir: &str, date: &str, format: &str) -> PathBuf {
+    Path::new(dir)
+        .join(format!("date={}", date))
+        .join(format!("part.{}", format))
+}
+
+fn read_schema(path: &Path, format: &str) -> Result<SchemaRef, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let schema = if format == "arrow" {
+        FileReader::try_new(file, None)?.schema()
+    } else {
+        Arc::clone(ParquetRecordBatchReaderBuilder::try_new(file)?.schema())
+    };
+    Ok(schema)
+}
+
+/// Writes to a temporary file first, so that no partial partition is left if writing fails.
+fn write_part(
+    path: &Path,
+    format: &str,
+    schema: &SchemaRef,
+    batch: &RecordBatch,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(path.parent().unwrap())?;
+    let tmp = path.with_extension("tmp");
+    let file = File::create(&tmp)?;
+    if format == "arrow" {
+        let mut writer = FileWriter::try_new(file, schema)?;
+        writer.write(batch)?;
+        writer.finish()?;
+    } else {
+        let mut writer = ArrowWriter::try_new(file, Arc::clone(schema), None)?;
+        writer.write(batch)?;
+        writer.close()?;
+    }
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stats::Player;
+    use arrow::array::Array;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn cache_file(dir: &Path, date: &str, players: &[Player]) -> (String, String) {
+        let filepath = dir.join(format!("{}.json", date));
+        fs::write(&filepath, serde_json::to_string(players).unwrap()).unwrap();
+        (date.to_string(), filepath.to_string_lossy().to_string())
+    }
+
+    fn batter<'a>(name: &'a str, home_runs: u32) -> Player<'a> {
+        Player::new_batter(
+            name,
+            "1B",
+            "1B",
+            BatterStats {
+                at_bats: 4,
+                home_runs,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn record_batch_should_have_row_per_player_with_nulls_for_other_role() {
+        let players = vec![
+            batter("Pete Alonso", 1),
+            Player::new_pitcher(
+                "Jacob deGrom",
+                "SP",
+                "SP",
+                PitcherStats {
+                    innings_pitched: 7.0,
+                    strikeouts: 11,
+                    ..Default::default()
+                },
+            ),
+            batter("Pete Alonso", 2),
+        ];
+        let batch = record_batch(&Arc::new(schema(false)), 18000, &players, None).unwrap();
+        assert_eq!(2, batch.num_rows());
+        assert_eq!(9 + 17 + 1 + 16, batch.num_columns());
+
+        let column = |name: &str| batch.column(batch.schema().index_of(name).unwrap()).clone();
+        let names = column("name");
+        let names = names.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!("Jacob deGrom", names.value(0));
+        assert_eq!("Pete Alonso", names.value(1));
+
+        let home_runs = column("b_home_runs");
+        let home_runs = home_runs.as_any().downcast_ref::<UInt32Array>().unwrap();
+        assert!(home_runs.is_null(0));
+        assert_eq!(3, home_runs.value(1));
+
+        let strikeouts = column("p_strikeouts");
+        let strikeouts = strikeouts.as_any().downcast_ref::<UInt32Array>().unwrap();
+        assert_eq!(11, strikeouts.value(0));
+        assert!(strikeouts.is_null(1));
+        assert!(column("mlb_team").is_null(0));
+    }
+
+    fn read_batches(path: &Path, format: &str) -> Vec<RecordBatch> {
+        let file = File::open(path).unwrap();
+        if format == "arrow" {
+            FileReader::try_new(file, None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        } else {
+            ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        }
+    }
+
+    fn dates(batches: &[RecordBatch]) -> Vec<i32> {
+        batches
+            .iter()
+            .flat_map(|b| {
+                b.column(0)
+                    .as_any()
+                    .downcast_ref::<Date32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_should_write_new_dates_only() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        for format in &["parquet", "arrow"] {
+            let dir = std::env::temp_dir().join(format!(
+                "mlbh2h-export-{}-{}-{}",
+                format,
+                std::process::id(),
+                nanos
+            ));
+            let cache = dir.join("cache");
+            fs::create_dir_all(&cache).unwrap();
+            let out = dir.join("out");
+            let out = out.to_str().unwrap();
+
+            let mut cached = vec![
+                cache_file(&cache, "2019-07-20", &[batter("Pete Alonso", 1)]),
+                cache_file(&cache, "notes", &[]),
+            ];
+            assert_eq!((1, 1), export(out, format, &cached, None, false).unwrap());
+            let first = part_path(out, "2019-07-20", format);
+            let modified = fs::metadata(&first).unwrap().modified().unwrap();
+
+            cached.push(cache_file(
+                &cache,
+                "2019-07-21",
+                &[batter("Pete Alonso", 0), batter("Mike Trout", 2)],
+            ));
+            assert_eq!((1, 2), export(out, format, &cached, None, false).unwrap());
+            assert_eq!((0, 0), export(out, format, &cached, None, false).unwrap());
+            assert_eq!(modified, fs::metadata(&first).unwrap().modified().unwrap());
+
+            assert_eq!(
+                vec![days("2019-07-20").unwrap()],
+                dates(&read_batches(&first, format))
+            );
+            let second = read_batches(&part_path(out, "2019-07-21", format), format);
+            assert_eq!(vec![18098, 18098], dates(&second));
+
+            let s = scoring::load(&"sample".to_string()).unwrap();
+            assert!(export(out, format, &cached, Some(&s), false).is_err());
+            assert_eq!(
+                (2, 3),
+                export(out, format, &cached, Some(&s), true).unwrap()
+            );
+
+            fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use crate::stats::output;
+use crate::stats::FantasyPlayer;
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct InvalidFilter(String, String);
+impl fmt::Display for InvalidFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid filter expression {:?}: {}", self.0, self.1)
+    }
+}
+impl Error for InvalidFilter {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A filter of the player tables by `--where`, e.g. `team == "LA Bulls" && B.HR >= 2`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(&'static str, Op, String),
+    In(&'static str, Vec<String>),
+}
+impl Expr {
+    /// Comparisons of stats not applicable to the player, e.g. `B.HR` of a pitcher, are false.
+    pub fn matches(&self, fp: &FantasyPlayer) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(fp) && b.matches(fp),
+            Expr::Or(a, b) => a.matches(fp) || b.matches(fp),
+            Expr::Not(e) => !e.matches(fp),
+            Expr::Compare(header, op, value) => {
+                let cell = output::get_stat_value(fp, header);
+                !cell.is_empty() && compare(*op, compare_values(&cell, value))
+            }
+            Expr::In(header, values) => {
+                let cell = output::get_stat_value(fp, header);
+                !cell.is_empty()
+                    && values
+                        .iter()
+                        .any(|v| compare_values(&cell, v) == Ordering::Equal)
+            }
+        }
+    }
+}
+
+fn compare(op: Op, ord: Ordering) -> bool {
+    match op {
+        Op::Eq => ord == Ordering::Equal,
+        Op::Ne => ord != Ordering::Equal,
+        Op::Lt => ord == Ordering::Less,
+        Op::Le => ord != Ordering::Greater,
+        Op::Gt => ord == Ordering::Greater,
+        Op::Ge => ord != Ordering::Less,
+    }
+}
+
+/// Numbers are compared numerically, and the others case-insensitively.
+fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f32>(), b.parse::<f32>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+        let (token, len) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('=', Some('=')) => (Token::Op(Op::Eq), 2),
+            ('!', Some('=')) => (Token::Op(Op::Ne), 2),
+            ('<', Some('=')) => (Token::Op(Op::Le), 2),
+            ('>', Some('=')) => (Token::Op(Op::Ge), 2),
+            ('<', _) => (Token::Op(Op::Lt), 1),
+            ('>', _) => (Token::Op(Op::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            (',', _) => (Token::Comma, 1),
+            ('"', _) | ('\'', _) => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|x| *x == c)
+                    .ok_or_else(|| "unterminated string".to_string())?;
+                let value: String = chars[i + 1..i + 1 + end].iter().collect();
+                (Token::Str(value), end + 2)
+            }
+            (c, _) if is_word_char(c) => {
+                let len = chars[i..].iter().take_while(|x| is_word_char(**x)).count();
+                (Token::Word(chars[i..i + len].iter().collect()), len)
+            }
+            (c, _) => return Err(format!("unexpected character {:?}", c)),
+        };
+        tokens.push(token);
+        i += len;
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '.' || c == '_' || c == '-' || c == '+'
+}
+
+/// Recursive descent parser of `or := and (|| and)*`, `and := unary (&& unary)*`,
+/// `unary := ! unary | ( or ) | FIELD OP VALUE | FIELD in (VALUE, ...)`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(ref t) if *t == expected => Ok(()),
+            Some(t) => Err(format!("expected {:?}, found {:?}", expected, t)),
+            None => Err(format!("expected {:?} at the end", expected)),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Word(field)) => self.condition(&field),
+            Some(t) => Err(format!("expected a field, found {:?}", t)),
+            None => Err("expected a field at the end".to_string()),
+        }
+    }
+
+    fn condition(&mut self, field: &str) -> Result<Expr, String> {
+        let header = output::HEADERS
+            .iter()
+            .find(|h| h.eq_ignore_ascii_case(field))
+            .ok_or_else(|| format!("unknown field {}", field))?;
+        match self.next() {
+            Some(Token::Op(op)) => Ok(Expr::Compare(header, op, self.value()?)),
+            Some(Token::Word(ref w)) if w.eq_ignore_ascii_case("in") => {
+                self.expect(Token::LParen)?;
+                let mut values = vec![self.value()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.next();
+                    values.push(self.value()?);
+                }
+                self.expect(Token::RParen)?;
+                Ok(Expr::In(header, values))
+            }
+            Some(t) => Err(format!(
+                "expected an operator after {}, found {:?}",
+                field, t
+            )),
+            None => Err(format!("expected an operator after {}", field)),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Word(v)) | Some(Token::Str(v)) => Ok(v),
+            Some(t) => Err(format!("expected a value, found {:?}", t)),
+            None => Err("expected a value at the end".to_string()),
+        }
+    }
+}
+
+pub fn parse(s: &str) -> Result<Expr, InvalidFilter> {
+    let invalid = |msg| InvalidFilter(s.to_string(), msg);
+    let mut parser = Parser {
+        tokens: tokenize(s).map_err(invalid)?,
+        pos: 0,
+    };
+    let expr = parser.or().map_err(invalid)?;
+    match parser.peek() {
+        Some(t) => Err(invalid(format!("unexpected {:?}", t))),
+        None => Ok(expr),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stats::{BatterStats, PitcherStats, Player};
+    use std::borrow::Cow;
+    use std::rc::Rc;
+
+    fn batter<'a>(team: &'a str, home_runs: u32) -> FantasyPlayer<'a> {
+        FantasyPlayer {
+            team: Rc::new(Cow::Borrowed(team)),
+            player: Player::new_batter(
+                "Trey Mancini",
+                "OF",
+                "RF",
+                BatterStats {
+                    home_runs,
+                    ..Default::default()
+                },
+            ),
+            fantasy_points: 12.5,
+        }
+    }
+
+    fn pitcher<'a>(position: &'a str, fantasy_points: f32) -> FantasyPlayer<'a> {
+        FantasyPlayer {
+            team: Rc::new(Cow::Borrowed("<FA>")),
+            player: Player::new_pitcher("Blake Snell", "P", position, PitcherStats::default()),
+            fantasy_points,
+        }
+    }
+
+    #[test]
+    fn parse_should_build_expression_with_precedence() {
+        assert_eq!(
+            Expr::Or(
+                Box::new(Expr::Compare("Team", Op::Eq, "LA Bulls".to_string())),
+                Box::new(Expr::And(
+                    Box::new(Expr::Compare("B.HR", Op::Ge, "2".to_string())),
+                    Box::new(Expr::Not(Box::new(Expr::In(
+                        "Pos",
+                        vec!["SP".to_string(), "RP".to_string()]
+                    )))),
+                )),
+            ),
+            parse("team == 'LA Bulls' || b.hr>=2 && !(pos in (SP, RP))").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_should_return_error_for_invalid_expression() {
+        assert!(parse("HR >= 2").is_err());
+        assert!(parse("B.HR >= ").is_err());
+        assert!(parse("team == \"LA Bulls").is_err());
+        assert!(parse("pos in (SP, RP").is_err());
+        assert!(parse("B.HR >= 2 B.R").is_err());
+        assert!(parse("B.HR ~ 2").is_err());
+    }
+
+    #[test]
+    fn matches_should_compare_fields_of_player() {
+        let expr = parse("team == \"LA Bulls\" && B.HR >= 2").unwrap();
+        assert!(expr.matches(&batter("LA Bulls", 2)));
+        assert!(expr.matches(&batter("la bulls", 3)));
+        assert!(!expr.matches(&batter("LA Bulls", 1)));
+        assert!(!expr.matches(&batter("NY Hotdogs", 2)));
+
+        let expr = parse("pos in (SP, RP) && FanPts > 20").unwrap();
+        assert!(expr.matches(&pitcher("SP", 20.5)));
+        assert!(!expr.matches(&pitcher("SP", 20.0)));
+        assert!(!expr.matches(&batter("LA Bulls", 2)));
+
+        let expr = parse("B.HR < 1").unwrap();
+        assert!(!expr.matches(&pitcher("SP", 20.5)));
+        assert!(parse("!(B.HR < 1)").unwrap().matches(&pitcher("SP", 20.5)));
+    }
+}
@@ -0,0 +1,428 @@
+use crate::stats;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use unicode_width::UnicodeWidthStr;
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Enables ANSI colors of the pretty format, e.g. if stdout is a terminal and `--no-color` is not set.
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+/// Alignment of the cells of a column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// A column of a table. `width` is the minimum display width in the pretty format,
+/// including the space to the next column.
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    pub width: usize,
+    pub align: Align,
+    pub truncate: bool,
+    pub points: bool,
+    pub top: usize,
+}
+impl Column {
+    pub fn new(name: &str, width: usize) -> Column {
+        Column {
+            name: name.to_string(),
+            width,
+            align: Align::Left,
+            truncate: false,
+            points: false,
+            top: 0,
+        }
+    }
+
+    pub fn right(mut self) -> Column {
+        self.align = Align::Right;
+        self
+    }
+
+    /// Cuts the cells longer than the width in the pretty format instead of widening the column.
+    pub fn truncated(mut self) -> Column {
+        self.truncate = true;
+        self
+    }
+
+    /// Colors positive numbers green and negative ones red in the pretty format.
+    pub fn points(mut self) -> Column {
+        self.points = true;
+        self
+    }
+
+    /// Highlights the rows of the n highest numbers of the column in the pretty format,
+    /// unless the table has n rows or less.
+    pub fn top(mut self, n: usize) -> Column {
+        self.top = n;
+        self
+    }
+}
+
+/// Renders the headings and the tables of the reports. Adding an output format means implementing this trait.
+pub trait Formatter {
+    /// Returns a heading, e.g. `# Team Rankings` for level 1. Level 0 is the title of a report.
+    fn heading(&self, level: usize, text: &str) -> String;
+
+    /// Returns the lines of a table including the header.
+    fn table(&self, columns: &[Column], rows: &[Vec<String>]) -> String;
+}
+
+#[derive(Debug, Clone)]
+pub struct UnsupportedFormat(pub String);
+impl fmt::Display for UnsupportedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "format {} is not supported by this command, use pretty, csv or markdown",
+            self.0
+        )
+    }
+}
+impl Error for UnsupportedFormat {}
+
+/// Returns the formatter of the `--format` option. JSON formats are printed by the commands
+/// supporting them, so they are unsupported here as the unknown formats.
+pub fn formatter(format: &str) -> Result<Box<dyn Formatter>, Box<dyn Error>> {
+    match format {
+        "pretty" => Ok(Box::new(Pretty {
+            color: COLOR.load(Ordering::Relaxed),
+        })),
+        "csv" => Ok(Box::new(Csv)),
+        "markdown" => Ok(Box::new(Markdown)),
+        _ => Err(Box::new(UnsupportedFormat(format.to_string()))),
+    }
+}
+
+/// Columns sized to the display width of the cells, so that accented and wide characters are aligned.
+pub struct Pretty {
+    pub color: bool,
+}
+impl Pretty {
+    /// Returns the indices of the rows to highlight by the `top` of the columns.
+    fn top_rows(columns: &[Column], rows: &[Vec<String>]) -> Vec<usize> {
+        let (i, n) = match columns.iter().position(|c| c.top > 0) {
+            Some(i) if rows.len() > columns[i].top => (i, columns[i].top),
+            _ => return vec![],
+        };
+        let mut values: Vec<(usize, f32)> = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(r, row)| {
+                row.get(i)
+                    .and_then(|c| c.parse::<f32>().ok())
+                    .map(|v| (r, v))
+            })
+            .collect();
+        values.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        values.into_iter().take(n).map(|(r, _)| r).collect()
+    }
+
+    fn style(&self, column: &Column, cell: &str, highlight: bool) -> String {
+        if !self.color || cell.is_empty() {
+            return cell.to_string();
+        }
+        let mut codes = vec![];
+        if highlight {
+            codes.push("1");
+        }
+        if cell == stats::FA {
+            codes.push("2");
+        }
+        if column.points {
+            match cell.parse::<f32>() {
+                Ok(v) if v > 0.0 => codes.push("32"),
+                Ok(v) if v < 0.0 => codes.push("31"),
+                _ => (),
+            }
+        }
+        if codes.is_empty() {
+            return cell.to_string();
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), cell)
+    }
+}
+impl Formatter for Pretty {
+    fn heading(&self, level: usize, text: &str) -> String {
+        hash_heading(level, text)
+    }
+
+    fn table(&self, columns: &[Column], rows: &[Vec<String>]) -> String {
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                if c.truncate {
+                    return c.width;
+                }
+                rows.iter()
+                    .filter_map(|r| r.get(i))
+                    .chain(std::iter::once(&c.name))
+                    .map(|cell| width(cell) + 1)
+                    .fold(c.width, usize::max)
+            })
+            .collect();
+        let top_rows = Pretty::top_rows(columns, rows);
+
+        let line = |cells: &mut dyn Iterator<Item = &String>, highlight: bool| {
+            cells
+                .zip(columns.iter().zip(widths.iter()))
+                .map(|(cell, (c, &w))| {
+                    let cell = truncate(cell, w.saturating_sub(1));
+                    let pad = " ".repeat(w.saturating_sub(1 + width(&cell)));
+                    let cell = self.style(c, &cell, highlight);
+                    match c.align {
+                        Align::Left => format!("{}{} ", cell, pad),
+                        Align::Right => format!("{}{} ", pad, cell),
+                    }
+                })
+                .collect::<String>()
+        };
+
+        let mut lines = vec![line(&mut columns.iter().map(|c| &c.name), false)];
+        lines.extend(
+            rows.iter()
+                .enumerate()
+                .map(|(i, r)| line(&mut r.iter(), top_rows.contains(&i))),
+        );
+        lines.join("\n")
+    }
+}
+
+pub struct Csv;
+impl Formatter for Csv {
+    fn heading(&self, level: usize, text: &str) -> String {
+        hash_heading(level, text)
+    }
+
+    fn table(&self, columns: &[Column], rows: &[Vec<String>]) -> String {
+        let line = |cells: &mut dyn Iterator<Item = &String>| {
+            cells.map(|c| quote(c)).collect::<Vec<_>>().join(",")
+        };
+
+        let mut lines = vec![line(&mut columns.iter().map(|c| &c.name))];
+        lines.extend(rows.iter().map(|r| line(&mut r.iter())));
+        lines.join("\n")
+    }
+}
+
+/// GitHub-flavored markdown tables, padded so that they are aligned as plain text too.
+pub struct Markdown;
+impl Formatter for Markdown {
+    fn heading(&self, level: usize, text: &str) -> String {
+        format!("{} {}", "#".repeat(level + 2), text)
+    }
+
+    fn table(&self, columns: &[Column], rows: &[Vec<String>]) -> String {
+        let headers: Vec<String> = columns.iter().map(|c| escape(&c.name)).collect();
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|r| r.iter().map(|c| escape(c)).collect())
+            .collect();
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                rows.iter()
+                    .filter_map(|r| r.get(i))
+                    .map(|c| width(c))
+                    .fold(width(h).max(3), usize::max)
+            })
+            .collect();
+
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(columns.iter().zip(widths.iter()))
+                .map(|(cell, (c, &w))| {
+                    let pad = " ".repeat(w - width(cell));
+                    match c.align {
+                        Align::Left => format!("{}{}", cell, pad),
+                        Align::Right => format!("{}{}", pad, cell),
+                    }
+                })
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let separator = columns
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, &w)| match c.align {
+                Align::Left => format!(":{}", "-".repeat(w + 1)),
+                Align::Right => format!("{}:", "-".repeat(w + 1)),
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let mut lines = vec![line(&headers), format!("|{}|", separator)];
+        lines.extend(rows.iter().map(|r| line(r)));
+        lines.join("\n")
+    }
+}
+
+/// Returns the number of terminal columns of the text, e.g. 2 for a CJK character and 0 for a combining mark.
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cuts the text to the display width.
+fn truncate(text: &str, w: usize) -> String {
+    let mut total = 0;
+    text.chars()
+        .take_while(|c| {
+            total += UnicodeWidthStr::width(c.encode_utf8(&mut [0; 4]) as &str);
+            total <= w
+        })
+        .collect()
+}
+
+fn hash_heading(level: usize, text: &str) -> String {
+    if level == 0 {
+        return text.to_string();
+    }
+    format!("{} {}", "#".repeat(level), text)
+}
+
+/// Quotes a CSV field containing commas, quotes or line breaks, doubling the quotes.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes the characters which would break a markdown table or be rendered as markup, e.g. `<FA>`.
+fn escape(cell: &str) -> String {
+    cell.chars().fold(String::new(), |mut acc, c| {
+        if "\\|*_`[]<>".contains(c) {
+            acc.push('\\');
+        }
+        acc.push(c);
+        acc
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn columns() -> Vec<Column> {
+        vec![
+            Column::new("Player", 8).truncated(),
+            Column::new("Team", 5),
+            Column::new("FanPts", 8).right(),
+        ]
+    }
+
+    fn rows() -> Vec<Vec<String>> {
+        vec![
+            vec![
+                "José Berríos".to_string(),
+                "<FA>".to_string(),
+                "21.50".to_string(),
+            ],
+            vec![
+                "A|B_C".to_string(),
+                "LA Bulls".to_string(),
+                "3.00".to_string(),
+            ],
+        ]
+    }
+
+    #[test]
+    fn formatter_should_reject_json_and_unknown_formats() {
+        assert!(formatter("pretty").is_ok());
+        assert!(formatter("csv").is_ok());
+        assert!(formatter("markdown").is_ok());
+        let e = formatter("ndjson").err().unwrap();
+        assert_eq!(
+            "format ndjson is not supported by this command, use pretty, csv or markdown",
+            e.to_string()
+        );
+        assert!(formatter("json").is_err());
+        assert!(formatter("").is_err());
+    }
+
+    #[test]
+    fn pretty_table_should_pad_truncate_and_widen_columns() {
+        assert_eq!(
+            [
+                "Player  Team      FanPts ",
+                "José Be <FA>       21.50 ",
+                "A|B_C   LA Bulls    3.00 ",
+            ]
+            .join("\n"),
+            Pretty { color: false }.table(&columns(), &rows())
+        );
+    }
+
+    #[test]
+    fn pretty_table_should_align_wide_characters() {
+        let rows = vec![vec![
+            "大谷翔平 Shohei".to_string(),
+            "Angels".to_string(),
+            "8.00".to_string(),
+        ]];
+        assert_eq!(
+            ["Player  Team    FanPts ", "大谷翔  Angels    8.00 ",].join("\n"),
+            Pretty { color: false }.table(&columns(), &rows)
+        );
+    }
+
+    #[test]
+    fn pretty_table_should_color_points_and_highlight_top_rows() {
+        let columns = vec![
+            Column::new("Team", 5),
+            Column::new("FanPts", 8).right().points().top(1),
+        ];
+        let rows: Vec<Vec<String>> = [("<FA>", "-1.50"), ("Bulls", "3.00"), ("Pizza", "0.00")]
+            .iter()
+            .map(|(t, p)| vec![t.to_string(), p.to_string()])
+            .collect();
+        assert_eq!(
+            [
+                "Team   FanPts ",
+                "\u{1b}[2m<FA>\u{1b}[0m    \u{1b}[31m-1.50\u{1b}[0m ",
+                "\u{1b}[1mBulls\u{1b}[0m    \u{1b}[1;32m3.00\u{1b}[0m ",
+                "Pizza    0.00 ",
+            ]
+            .join("\n"),
+            Pretty { color: true }.table(&columns, &rows)
+        );
+    }
+
+    #[test]
+    fn csv_table_should_quote_fields() {
+        let rows = vec![vec![
+            "Smith, Will".to_string(),
+            "The \"Nats\"".to_string(),
+            "3.00".to_string(),
+        ]];
+        assert_eq!(
+            "Player,Team,FanPts\n\"Smith, Will\",\"The \"\"Nats\"\"\",3.00",
+            Csv.table(&columns(), &rows)
+        );
+    }
+
+    #[test]
+    fn markdown_table_should_align_and_escape_cells() {
+        assert_eq!(
+            [
+                "| Player       | Team     | FanPts |",
+                "|:-------------|:---------|-------:|",
+                "| José Berríos | \\<FA\\>   |  21.50 |",
+                "| A\\|B\\_C      | LA Bulls |   3.00 |",
+            ]
+            .join("\n"),
+            Markdown.table(&columns(), &rows())
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::league::{roster, scoring};
+use crate::stats::{self, format, output, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct PlayerNotFound(String, String, String);
+impl fmt::Display for PlayerNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no stats found for player {} from {} to {}",
+            self.0, self.1, self.2
+        )
+    }
+}
+impl Error for PlayerNotFound {}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let name = matches.value_of("name").unwrap();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let mut dates = utils::date_strs(&config.date, &config.range);
+    dates.sort();
+
+    let players: Vec<_> = dates
+        .iter()
+        .map(|d| (d.clone(), stats::players_for_date(d.clone(), &config)))
+        .collect();
+    let (batting, pitching) = game_logs(&players, name, &s, &r);
+    if batting.is_empty() && pitching.is_empty() {
+        return Err(Box::new(PlayerNotFound(
+            name.to_string(),
+            dates.first().unwrap().clone(),
+            dates.last().unwrap().clone(),
+        )));
+    }
+
+    for (title, games) in [("Batting", &batting), ("Pitching", &pitching)].iter() {
+        if games.is_empty() {
+            continue;
+        }
+        let fp = &games[0].1;
+        println!(
+            "{}",
+            f.heading(
+                1,
+                &format!(
+                    "{} Game Log: {} ({}, {} ~ {})",
+                    title,
+                    fp.player.name,
+                    fp.team,
+                    dates.first().unwrap(),
+                    dates.last().unwrap()
+                )
+            )
+        );
+        output::print_game_log(games, &s, f.as_ref());
+        println!();
+    }
+
+    Ok(())
+}
+
+pub(super) type GameLog<'a> = Vec<(String, FantasyPlayer<'a>)>;
+
+/// Returns the batting and the pitching games of the player, one row per game
+/// (games of a doubleheader are not merged).
+pub(super) fn game_logs<'a>(
+    players: &[(String, Vec<stats::Player<'a>>)],
+    name: &str,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster<'a>,
+) -> (GameLog<'a>, GameLog<'a>) {
+    let mut batting = vec![];
+    let mut pitching = vec![];
+
+    for (date, ps) in players.iter() {
+        ps.iter()
+            .filter(|p| p.name.to_lowercase() == name.to_lowercase())
+            .for_each(|p| {
+                let fp = stats::to_fantasy_player(p, s, r);
+                if fp.player.pitcher_stats.is_some() {
+                    pitching.push((date.clone(), fp));
+                } else {
+                    batting.push((date.clone(), fp));
+                }
+            });
+    }
+
+    (batting, pitching)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, Player};
+
+    #[test]
+    fn game_logs_should_return_a_row_per_game() {
+        let batter = |name| {
+            Player::new_batter(
+                name,
+                "OF",
+                "RF",
+                BatterStats {
+                    at_bats: 4,
+                    hits: 1,
+                    singles: 1,
+                    ..Default::default()
+                },
+            )
+        };
+        let players = vec![
+            (
+                "2019-07-01".to_string(),
+                vec![batter("Cody Bellinger"), batter("Mike Trout")],
+            ),
+            ("2019-07-02".to_string(), vec![batter("Mike Trout")]),
+            (
+                "2019-07-03".to_string(),
+                vec![batter("Cody Bellinger"), batter("Cody Bellinger")],
+            ),
+        ];
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+
+        let (batting, pitching) = game_logs(&players, "cody bellinger", &s, &r);
+        assert_eq!(
+            vec!["2019-07-01", "2019-07-03", "2019-07-03"],
+            batting.iter().map(|(d, _)| d.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!("LA Bulls", batting[0].1.team.as_ref());
+        assert!(pitching.is_empty());
+    }
+}
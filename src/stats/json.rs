@@ -0,0 +1,359 @@
+use crate::stats::columns;
+use crate::stats::consistency::Consistency;
+use crate::stats::{output, BatterStats, Config, FantasyPlayer, PitcherStats};
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Returns true for the `json` (a document per command) and `ndjson` (a record per line) formats.
+pub fn is_json(format: &str) -> bool {
+    format == "json" || format == "ndjson"
+}
+
+/// A player row. Stats of the other role are null, and consistency is null unless requested.
+#[derive(Serialize, Debug)]
+struct PlayerRow<'a> {
+    name: &'a str,
+    team: &'a str,
+    mlb_team: &'a str,
+    position: &'a str,
+    fantasy_points: f32,
+    batter_stats: Option<&'a BatterStats>,
+    pitcher_stats: Option<&'a PitcherStats>,
+    consistency: Option<&'a Consistency>,
+}
+impl<'a> PlayerRow<'a> {
+    fn new(fp: &'a FantasyPlayer, consistency: &'a HashMap<String, Consistency>) -> PlayerRow<'a> {
+        PlayerRow {
+            name: &fp.player.name,
+            team: &fp.team,
+            mlb_team: &fp.player.mlb_team,
+            position: &fp.player.primary_position,
+            fantasy_points: fp.fantasy_points,
+            batter_stats: fp.player.batter_stats.as_ref(),
+            pitcher_stats: fp.player.pitcher_stats.as_ref(),
+            consistency: consistency.get(&fp.key()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct TeamPoints<'a> {
+    team: &'a str,
+    fantasy_points: f32,
+}
+
+#[derive(Serialize, Debug)]
+struct TeamRanking<'a> {
+    rank: usize,
+    team: &'a str,
+    fantasy_points: f32,
+}
+
+#[derive(Serialize, Debug)]
+struct DailyPoints<'a> {
+    date: &'a str,
+    teams: Vec<TeamPoints<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct DatedPlayerRow<'a> {
+    date: &'a str,
+    #[serde(flatten)]
+    player: PlayerRow<'a>,
+}
+
+/// The document of the `json` format for the players of the range.
+#[derive(Serialize, Debug)]
+struct PlayersDocument<'a> {
+    date: &'a str,
+    range: &'a str,
+    players: Option<Vec<PlayerRow<'a>>>,
+    top_batters: Option<Vec<PlayerRow<'a>>>,
+    top_pitchers: Option<Vec<PlayerRow<'a>>>,
+    team_rankings: Vec<TeamRanking<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct WeeklyChangesDocument<'a> {
+    date: &'a str,
+    weekly_changes: Vec<DailyPoints<'a>>,
+    totals: Vec<TeamPoints<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct OutstandingPlayersDocument<'a> {
+    date: &'a str,
+    outstanding_players: Vec<DatedPlayerRow<'a>>,
+}
+
+/// A line of the `ndjson` format, tagged by `type`.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record<'a> {
+    Player {
+        date: &'a str,
+        range: &'a str,
+        #[serde(flatten)]
+        player: PlayerRow<'a>,
+    },
+    TopBatter {
+        date: &'a str,
+        range: &'a str,
+        rank: usize,
+        #[serde(flatten)]
+        player: PlayerRow<'a>,
+    },
+    TopPitcher {
+        date: &'a str,
+        range: &'a str,
+        rank: usize,
+        #[serde(flatten)]
+        player: PlayerRow<'a>,
+    },
+    TeamRanking {
+        date: &'a str,
+        range: &'a str,
+        #[serde(flatten)]
+        ranking: TeamRanking<'a>,
+    },
+    WeeklyChange {
+        date: &'a str,
+        #[serde(flatten)]
+        points: TeamPoints<'a>,
+    },
+    WeeklyTotal {
+        #[serde(flatten)]
+        points: TeamPoints<'a>,
+    },
+    OutstandingPlayer {
+        #[serde(flatten)]
+        player: DatedPlayerRow<'a>,
+    },
+}
+
+fn print_records(records: Vec<Record>) -> Result<(), Box<dyn Error>> {
+    for r in records.iter() {
+        println!("{}", serde_json::to_string(r)?);
+    }
+    Ok(())
+}
+
+fn rows<'a>(
+    fps: &'a [FantasyPlayer],
+    consistency: &'a HashMap<String, Consistency>,
+) -> Vec<PlayerRow<'a>> {
+    fps.iter()
+        .map(|fp| PlayerRow::new(fp, consistency))
+        .collect()
+}
+
+fn team_points<'a>(pts: &'a [(String, f32)]) -> Vec<TeamPoints<'a>> {
+    pts.iter()
+        .map(|(team, fantasy_points)| TeamPoints {
+            team,
+            fantasy_points: *fantasy_points,
+        })
+        .collect()
+}
+
+fn rankings<'a>(scores: &'a [(String, f32)]) -> Vec<TeamRanking<'a>> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(i, (team, pts))| TeamRanking {
+            rank: i + 1,
+            team,
+            fantasy_points: *pts,
+        })
+        .collect()
+}
+
+/// Prints the players (or the top batters and pitchers with --topn) and the team rankings.
+/// Team rankings are of all the players, and the others are of the players kept by `columns`.
+pub fn print_fantasy_players(
+    players: &[FantasyPlayer],
+    config: &Config,
+    consistency: &HashMap<String, Consistency>,
+    columns: &columns::Options,
+) -> Result<(), Box<dyn Error>> {
+    let (date, range) = (config.date.as_ref(), config.range.as_ref());
+    let scores = output::get_scores_per_team(players);
+    let players: &[FantasyPlayer] = &players
+        .iter()
+        .filter(|p| columns.keep(p))
+        .cloned()
+        .collect::<Vec<_>>();
+    let top = if config.top_n > 0 {
+        Some(output::top_players(players.to_vec(), config.top_n))
+    } else {
+        None
+    };
+    let rows = |fps| rows(fps, consistency);
+
+    if config.format == "ndjson" {
+        let mut records = vec![];
+        match &top {
+            Some((batters, pitchers)) => {
+                records.extend(rows(batters).into_iter().enumerate().map(|(i, player)| {
+                    Record::TopBatter {
+                        date,
+                        range,
+                        rank: i + 1,
+                        player,
+                    }
+                }));
+                records.extend(rows(pitchers).into_iter().enumerate().map(|(i, player)| {
+                    Record::TopPitcher {
+                        date,
+                        range,
+                        rank: i + 1,
+                        player,
+                    }
+                }));
+            }
+            None => records.extend(rows(players).into_iter().map(|player| Record::Player {
+                date,
+                range,
+                player,
+            })),
+        }
+        records.extend(
+            rankings(&scores)
+                .into_iter()
+                .map(|ranking| Record::TeamRanking {
+                    date,
+                    range,
+                    ranking,
+                }),
+        );
+        return print_records(records);
+    }
+
+    let document = PlayersDocument {
+        date,
+        range,
+        players: match top {
+            Some(_) => None,
+            None => Some(rows(players)),
+        },
+        top_batters: top.as_ref().map(|(batters, _)| rows(batters)),
+        top_pitchers: top.as_ref().map(|(_, pitchers)| rows(pitchers)),
+        team_rankings: rankings(&scores),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+pub fn print_weekly_changes(
+    changes: &[(String, Vec<(String, f32)>)],
+    totals: &[(String, f32)],
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    if config.format == "ndjson" {
+        let mut records = vec![];
+        changes.iter().for_each(|(date, pts)| {
+            records.extend(
+                team_points(pts)
+                    .into_iter()
+                    .map(|points| Record::WeeklyChange { date, points }),
+            );
+        });
+        records.extend(
+            team_points(totals)
+                .into_iter()
+                .map(|points| Record::WeeklyTotal { points }),
+        );
+        return print_records(records);
+    }
+
+    let document = WeeklyChangesDocument {
+        date: &config.date,
+        weekly_changes: changes
+            .iter()
+            .map(|(date, pts)| DailyPoints {
+                date,
+                teams: team_points(pts),
+            })
+            .collect(),
+        totals: team_points(totals),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+pub fn print_outstanding_players(
+    players: &[(String, FantasyPlayer)],
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    let no_consistency = HashMap::new();
+    let rows = players.iter().map(|(date, fp)| DatedPlayerRow {
+        date,
+        player: PlayerRow::new(fp, &no_consistency),
+    });
+
+    if config.format == "ndjson" {
+        return print_records(
+            rows.map(|player| Record::OutstandingPlayer { player })
+                .collect(),
+        );
+    }
+
+    let document = OutstandingPlayersDocument {
+        date: &config.date,
+        outstanding_players: rows.collect(),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stats::{BatterStats, Player};
+
+    #[test]
+    fn records_should_be_tagged_by_type() {
+        let fp = FantasyPlayer {
+            player: Player::new_batter(
+                "Cody Bellinger",
+                "OF",
+                "RF",
+                BatterStats {
+                    home_runs: 1,
+                    ..Default::default()
+                },
+            ),
+            fantasy_points: 4.0,
+            ..Default::default()
+        };
+        let no_consistency = HashMap::new();
+        let record = Record::Player {
+            date: "2019-07-01",
+            range: "1d",
+            player: PlayerRow::new(&fp, &no_consistency),
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+        assert_eq!("player", json["type"]);
+        assert_eq!("2019-07-01", json["date"]);
+        assert_eq!("Cody Bellinger", json["name"]);
+        assert_eq!(4.0, json["fantasy_points"]);
+        assert_eq!(1, json["batter_stats"]["home_runs"]);
+        assert!(json["pitcher_stats"].is_null());
+        assert!(json["consistency"].is_null());
+
+        let record = Record::WeeklyTotal {
+            points: TeamPoints {
+                team: "LA Bulls",
+                fantasy_points: 10.5,
+            },
+        };
+        assert_eq!(
+            r#"{"type":"weekly_total","team":"LA Bulls","fantasy_points":10.5}"#,
+            serde_json::to_string(&record).unwrap()
+        );
+    }
+}
@@ -1,655 +1,354 @@
 use crate::league::scoring::ScoringRule;
-use crate::stats::{Config, FantasyPlayer};
+use crate::stats::columns;
+use crate::stats::consistency::Consistency;
+use crate::stats::format::{Column, Formatter};
+use crate::stats::{self, BatterStats, Config, FantasyPlayer, PitcherStats};
 
-use std::borrow::Cow;
 use std::collections::HashMap;
-use std::rc::Rc;
 
-pub fn print_outstanding_player(date: String, fplayer: FantasyPlayer, s: &ScoringRule) {
-    let header_items = if fplayer.player.batter_stats.is_some() {
-        s.get_header_items_for_batter()
+pub fn print_outstanding_player(
+    date: String,
+    fplayer: FantasyPlayer,
+    s: &ScoringRule,
+    columns: &columns::Options,
+    f: &dyn Formatter,
+) {
+    let headers = columns.headers(s.get_header_items());
+    let header_items: Vec<String> = if fplayer.player.batter_stats.is_some() {
+        for_batter(&headers)
     } else {
-        s.get_header_items_for_pitcher()
-    };
+        for_pitcher(&headers)
+    }
+    .into_iter()
+    .filter(|h| h != "Team")
+    .collect();
 
-    println!("# {}", date);
+    println!("{}", f.heading(1, &date));
     println!(
         "{}",
-        get_header_string(&header_items, false).replace("Team      ", "")
+        f.table(
+            &get_stat_columns(&header_items),
+            &[get_stat_cells(&fplayer, &header_items)]
+        )
     );
+}
+
+/// Consistency columns are appended if `consistency` is not empty.
+pub fn print_fantasy_players(
+    players: Vec<FantasyPlayer>,
+    config: &Config,
+    s: &ScoringRule,
+    consistency: &HashMap<String, Consistency>,
+    columns: &columns::Options,
+    f: &dyn Formatter,
+) {
+    let headers = columns.headers(s.get_header_items());
+    let players: Vec<FantasyPlayer> = players.into_iter().filter(|p| columns.keep(p)).collect();
     println!(
         "{}",
-        get_player_stats_string(&fplayer, &header_items, false).replace("<FA>      ", "")
+        f.heading(0, &format!("{} ({})", config.date, config.range))
     );
-}
 
-pub fn print_fantasy_players(players: Vec<FantasyPlayer>, config: &Config, s: &ScoringRule) {
-    let date = &config.date;
-    println!("{} ({})", date, &config.range);
+    let table = |players: &[FantasyPlayer], headers: &[String]| {
+        let mut columns: Vec<Column> = get_stat_columns(headers)
+            .into_iter()
+            .map(|c| if c.name == "FanPts" { c.top(3) } else { c })
+            .collect();
+        if !consistency.is_empty() {
+            columns.extend(Consistency::columns());
+        }
+        let rows: Vec<Vec<String>> = players
+            .iter()
+            .map(|p| {
+                let mut cells = get_stat_cells(p, headers);
+                if !consistency.is_empty() {
+                    cells.extend(
+                        consistency
+                            .get(&p.key())
+                            .cloned()
+                            .unwrap_or_default()
+                            .cells(),
+                    );
+                }
+                cells
+            })
+            .collect();
+        f.table(&columns, &rows)
+    };
 
-    let header_items = s.get_header_items();
+    if config.top_n > 0 {
+        let (batters, pitchers) = top_players(players, config.top_n);
 
-    let is_csv = config.format == Cow::Borrowed("csv");
+        println!();
+        println!("{}", f.heading(2, &format!("Top {} Batters", config.top_n)));
+        println!("{}", table(&batters, &for_batter(&headers)));
 
-    if config.top_n > 0 {
-        let mut batters: Vec<FantasyPlayer> = vec![];
-        let mut pitchers: Vec<FantasyPlayer> = vec![];
-        for p in players.into_iter() {
-            if p.player.batter_stats.is_some() && batters.len() < config.top_n {
-                batters.push(p);
-            } else if p.player.pitcher_stats.is_some() && pitchers.len() < config.top_n {
-                pitchers.push(p);
-            }
-
-            if batters.len() >= config.top_n && pitchers.len() >= config.top_n {
-                break;
-            }
-        }
+        println!();
+        println!(
+            "{}",
+            f.heading(2, &format!("Top {} Pitchers", config.top_n))
+        );
+        println!("{}", table(&pitchers, &for_pitcher(&headers)));
+    } else {
+        println!("{}", table(&players, &headers));
+    }
+}
 
-        let batter_header_items = s.get_header_items_for_batter();
-        let pitcher_header_items = s.get_header_items_for_pitcher();
+fn for_batter(headers: &[String]) -> Vec<String> {
+    headers
+        .iter()
+        .filter(|h| !h.starts_with("P."))
+        .cloned()
+        .collect()
+}
 
-        println!("\n## Top {} Batters ##", config.top_n);
-        println!("{}", get_header_string(&batter_header_items, is_csv));
-        for p in batters.iter() {
-            println!(
-                "{}",
-                get_player_stats_string(p, &batter_header_items, is_csv)
-            );
-        }
+fn for_pitcher(headers: &[String]) -> Vec<String> {
+    headers
+        .iter()
+        .filter(|h| !h.starts_with("B."))
+        .cloned()
+        .collect()
+}
 
-        println!("\n## Top {} Pitchers ##", config.top_n);
-        println!("{}", get_header_string(&pitcher_header_items, is_csv));
-        for p in pitchers.iter() {
-            println!(
-                "{}",
-                get_player_stats_string(p, &pitcher_header_items, is_csv)
-            );
+/// Returns the first n batters and the first n pitchers of the players.
+pub fn top_players(
+    players: Vec<FantasyPlayer>,
+    n: usize,
+) -> (Vec<FantasyPlayer>, Vec<FantasyPlayer>) {
+    let mut batters: Vec<FantasyPlayer> = vec![];
+    let mut pitchers: Vec<FantasyPlayer> = vec![];
+    for p in players.into_iter() {
+        if p.player.batter_stats.is_some() && batters.len() < n {
+            batters.push(p);
+        } else if p.player.pitcher_stats.is_some() && pitchers.len() < n {
+            pitchers.push(p);
         }
-    } else {
-        println!("{}", get_header_string(&header_items, is_csv));
-        for p in players.iter() {
-            println!("{}", get_player_stats_string(p, &header_items, is_csv));
+
+        if batters.len() >= n && pitchers.len() >= n {
+            break;
         }
     }
+    (batters, pitchers)
+}
+
+/// Returns the columns of given stat headers, with numbers aligned to the right.
+pub fn get_stat_columns(headers: &[String]) -> Vec<Column> {
+    headers
+        .iter()
+        .map(|h| match h.as_str() {
+            "Player" => Column::new(h, 18),
+            "Team" => Column::new(h, 10),
+            "FanPts" => Column::new(h, 8).right().points(),
+            "Pos" => Column::new(h, 4),
+            "P.IP" => Column::new(h, 7).right(),
+            _ => Column::new(h, h.len() + 1).right(),
+        })
+        .collect()
+}
+
+/// Returns the values of given stat headers, empty for stats not applicable to the player.
+pub fn get_stat_cells(fp: &FantasyPlayer, headers: &[String]) -> Vec<String> {
+    headers.iter().map(|h| get_stat_value(fp, h)).collect()
 }
 
-fn get_header_string(headers: &[String], is_csv: bool) -> String {
-    if is_csv {
-        return headers.join(",");
+/// All the headers of the player tables, in the order of `ScoringRule::get_header_items`.
+pub const HEADERS: [&str; 38] = [
+    "Player", "Team", "FanPts", "Pos", "B.AB", "B.R", "B.H", "B.1B", "B.2B", "B.3B", "B.HR",
+    "B.RBI", "B.SAC", "B.SB", "B.CS", "B.BB", "B.IBB", "B.HBP", "B.K", "B.GIDP", "B.TB", "P.IP",
+    "P.W", "P.L", "P.CG", "P.SHO", "P.SV", "P.OUT", "P.H", "P.ER", "P.HR", "P.BB", "P.IBB",
+    "P.HBP", "P.K", "P.SB", "P.GIDP", "P.TB",
+];
+
+/// Returns the value of given stat header, empty if the stat is not applicable to the player.
+pub fn get_stat_value(fp: &FantasyPlayer, header: &str) -> String {
+    let b = |stat: fn(&BatterStats) -> u32| match &fp.player.batter_stats {
+        Some(s) => stat(s).to_string(),
+        None => "".to_string(),
+    };
+    let p = |stat: fn(&PitcherStats) -> u32| match &fp.player.pitcher_stats {
+        Some(s) => stat(s).to_string(),
+        None => "".to_string(),
+    };
+
+    match header {
+        "Player" => fp.player.name.to_string(),
+        "Team" => fp.team.to_string(),
+        "FanPts" => format!("{:.2}", fp.fantasy_points),
+        "Pos" => fp.player.primary_position.to_string(),
+        "B.AB" => b(|s| s.at_bats),
+        "B.R" => b(|s| s.runs),
+        "B.H" => b(|s| s.hits),
+        "B.1B" => b(|s| s.singles),
+        "B.2B" => b(|s| s.doubles),
+        "B.3B" => b(|s| s.triples),
+        "B.HR" => b(|s| s.home_runs),
+        "B.RBI" => b(|s| s.runs_batted_in),
+        "B.SAC" => b(|s| s.sacrifice_hits),
+        "B.SB" => b(|s| s.stolen_bases),
+        "B.CS" => b(|s| s.caught_stealing),
+        "B.BB" => b(|s| s.walks),
+        "B.IBB" => b(|s| s.intentional_walks),
+        "B.HBP" => b(|s| s.hit_by_pitch),
+        "B.K" => b(|s| s.strikeouts),
+        "B.GIDP" => b(|s| s.ground_into_double_play),
+        "B.TB" => b(|s| s.total_bases),
+        "P.IP" => match &fp.player.pitcher_stats {
+            Some(s) => ((s.innings_pitched * 10.0).round() / 10.0).to_string(),
+            None => "".to_string(),
+        },
+        "P.W" => p(|s| s.wins),
+        "P.L" => p(|s| s.losses),
+        "P.CG" => p(|s| s.complete_games),
+        "P.SHO" => p(|s| s.shutouts),
+        "P.SV" => p(|s| s.saves),
+        "P.OUT" => p(|s| s.outs),
+        "P.H" => p(|s| s.hits),
+        "P.ER" => p(|s| s.earned_runs),
+        "P.HR" => p(|s| s.home_runs),
+        "P.BB" => p(|s| s.walks),
+        "P.IBB" => p(|s| s.intentional_walks),
+        "P.HBP" => p(|s| s.hit_batters),
+        "P.K" => p(|s| s.strikeouts),
+        "P.SB" => p(|s| s.stolen_bases_allowed),
+        "P.GIDP" => p(|s| s.batters_grounded_into_double_plays),
+        "P.TB" => p(|s| s.total_bases_allowed),
+        _ => "".to_string(),
     }
+}
 
-    // for pretty-print format
-    let mut items = Vec::new();
-    for i in headers {
-        if i.contains("Player") {
-            items.push(format!("{:18}", "Player")); // 18bytes
-        } else if i.contains("Team") {
-            items.push(format!("{:10}", "Team")); // 10bytes
-        } else if i.contains("FanPts") {
-            items.push(format!("{:8}", "FanPts")); // 8bytes
-        } else if i.contains("P.IP") {
-            items.push(format!("{}   ", i));
-        } else {
-            items.push(format!("{} ", i));
-        }
+/// Prints one row per game, then the totals and the per game averages of the games.
+pub fn print_game_log(games: &[(String, FantasyPlayer)], s: &ScoringRule, f: &dyn Formatter) {
+    let first = match games.first() {
+        Some((_, fp)) => fp,
+        None => return,
+    };
+    let header_items: Vec<String> = if first.player.batter_stats.is_some() {
+        s.get_header_items_for_batter()
+    } else {
+        s.get_header_items_for_pitcher()
     }
+    .into_iter()
+    .filter(|h| h != "Player" && h != "Team")
+    .collect();
 
-    items.join("")
-}
+    let mut columns = vec![Column::new("Date", 12), Column::new("Opp", 6)];
+    columns.extend(get_stat_columns(&header_items));
+    let row = |date: &str, opponent: &str, cells: Vec<String>| {
+        let mut row = vec![date.to_string(), opponent.to_string()];
+        row.extend(cells);
+        row
+    };
 
-#[allow(clippy::cognitive_complexity)]
-fn get_player_stats_string(fp: &FantasyPlayer, headers: &[String], is_csv: bool) -> String {
-    let bstats = &fp.player.batter_stats;
-    let pstats = &fp.player.pitcher_stats;
+    let mut rows: Vec<Vec<String>> = games
+        .iter()
+        .map(|(date, fp)| row(date, &fp.player.opponent, get_stat_cells(fp, &header_items)))
+        .collect();
 
-    if is_csv {
-        headers
-            .iter()
-            .map(|h| match h.as_str() {
-                "Player" => fp.player.name.to_owned().into(),
-                "Team" => (*fp.team).to_owned().into(),
-                "FanPts" => format!("{:.2}", fp.fantasy_points),
-                "Pos" => fp.player.primary_position.to_owned().into(),
-                "B.AB" => {
-                    if let Some(s) = bstats {
-                        s.at_bats.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.R" => {
-                    if let Some(s) = bstats {
-                        s.runs.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.H" => {
-                    if let Some(s) = bstats {
-                        s.hits.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.1B" => {
-                    if let Some(s) = bstats {
-                        s.singles.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.2B" => {
-                    if let Some(s) = bstats {
-                        s.doubles.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.3B" => {
-                    if let Some(s) = bstats {
-                        s.triples.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.HR" => {
-                    if let Some(s) = bstats {
-                        s.home_runs.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.RBI" => {
-                    if let Some(s) = bstats {
-                        s.runs_batted_in.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.SAC" => {
-                    if let Some(s) = bstats {
-                        s.sacrifice_hits.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.SB" => {
-                    if let Some(s) = bstats {
-                        s.stolen_bases.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.CS" => {
-                    if let Some(s) = bstats {
-                        s.caught_stealing.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.BB" => {
-                    if let Some(s) = bstats {
-                        s.walks.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.IBB" => {
-                    if let Some(s) = bstats {
-                        s.intentional_walks.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.HBP" => {
-                    if let Some(s) = bstats {
-                        s.hit_by_pitch.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.K" => {
-                    if let Some(s) = bstats {
-                        s.strikeouts.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.GIDP" => {
-                    if let Some(s) = bstats {
-                        s.ground_into_double_play.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "B.TB" => {
-                    if let Some(s) = bstats {
-                        s.total_bases.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.IP" => {
-                    if let Some(s) = pstats {
-                        s.innings_pitched.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.W" => {
-                    if let Some(s) = pstats {
-                        s.wins.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.L" => {
-                    if let Some(s) = pstats {
-                        s.losses.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.CG" => {
-                    if let Some(s) = pstats {
-                        s.complete_games.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.SHO" => {
-                    if let Some(s) = pstats {
-                        s.shutouts.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.SV" => {
-                    if let Some(s) = pstats {
-                        s.saves.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.OUT" => {
-                    if let Some(s) = pstats {
-                        s.outs.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.H" => {
-                    if let Some(s) = pstats {
-                        s.hits.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.ER" => {
-                    if let Some(s) = pstats {
-                        s.earned_runs.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.HR" => {
-                    if let Some(s) = pstats {
-                        s.home_runs.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.BB" => {
-                    if let Some(s) = pstats {
-                        s.walks.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.IBB" => {
-                    if let Some(s) = pstats {
-                        s.intentional_walks.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.HBP" => {
-                    if let Some(s) = pstats {
-                        s.hit_batters.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.K" => {
-                    if let Some(s) = pstats {
-                        s.strikeouts.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.SB" => {
-                    if let Some(s) = pstats {
-                        s.stolen_bases_allowed.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.GIDP" => {
-                    if let Some(s) = pstats {
-                        s.batters_grounded_into_double_plays.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                "P.TB" => {
-                    if let Some(s) = pstats {
-                        s.total_bases_allowed.to_string()
-                    } else {
-                        "".to_string()
-                    }
-                }
-                _ => "".to_string(),
-            })
-            .collect::<Vec<_>>()
-            .join(",")
+    let total = games
+        .iter()
+        .skip(1)
+        .fold(first.clone(), |mut acc, (_, fp)| {
+            acc.add_stats(fp.clone());
+            acc
+        });
+    rows.push(row("Total", "", get_stat_cells(&total, &header_items)));
+    rows.push(row(
+        "Per game",
+        "",
+        get_per_game_cells(&total, games.len(), &header_items),
+    ));
+    println!("{}", f.table(&columns, &rows));
+}
+
+/// Prints one row per split with the number of games, the total stats and the fantasy points per game.
+pub fn print_splits(splits: &[(String, usize, FantasyPlayer)], s: &ScoringRule, f: &dyn Formatter) {
+    let first = match splits.first() {
+        Some((_, _, fp)) => fp,
+        None => return,
+    };
+    let header_items: Vec<String> = if first.player.batter_stats.is_some() {
+        s.get_header_items_for_batter()
     } else {
-        headers
-            .iter()
-            .map(|h| match h.as_str() {
-                "Player" => {
-                    let mut name = fp.player.name.to_owned().to_string();
-                    let mut new_len = 17;
-                    while !name.is_char_boundary(new_len) {
-                        new_len -= 1;
-                    }
-                    name.truncate(new_len);
-                    format!("{:18}", name)
-                }
-                "Team" => {
-                    let mut team = (*fp.team).to_owned().to_string();
-                    team.truncate(9);
-                    format!("{:10}", team)
-                }
-                "FanPts" => format!("{:7.2} ", fp.fantasy_points),
-                "Pos" => format!("{:4}", fp.player.primary_position.to_owned().to_string()),
-                "B.AB" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.at_bats.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.R" => {
-                    if let Some(s) = bstats {
-                        format!("{:4}", s.runs.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "B.H" => {
-                    if let Some(s) = bstats {
-                        format!("{:4}", s.hits.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "B.1B" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.singles.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.2B" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.doubles.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.3B" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.triples.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.HR" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.home_runs.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.RBI" => {
-                    if let Some(s) = bstats {
-                        format!("{:6}", s.runs_batted_in.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "B.SAC" => {
-                    if let Some(s) = bstats {
-                        format!("{:6}", s.sacrifice_hits.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "B.SB" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.stolen_bases.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.CS" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.caught_stealing.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.BB" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.walks.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "B.IBB" => {
-                    if let Some(s) = bstats {
-                        format!("{:6}", s.intentional_walks.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "B.HBP" => {
-                    if let Some(s) = bstats {
-                        format!("{:6}", s.hit_by_pitch.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "B.K" => {
-                    if let Some(s) = bstats {
-                        format!("{:4}", s.strikeouts.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "B.GIDP" => {
-                    if let Some(s) = bstats {
-                        format!("{:7}", s.ground_into_double_play.to_string())
-                    } else {
-                        format!("{:7}", "")
-                    }
-                }
-                "B.TB" => {
-                    if let Some(s) = bstats {
-                        format!("{:5}", s.total_bases.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.IP" => {
-                    if let Some(s) = pstats {
-                        let ip = (s.innings_pitched * 10.0).round() / 10.0;
-                        format!("{:7}", ip.to_string())
-                    } else {
-                        format!("{:7}", "")
-                    }
-                }
-                "P.W" => {
-                    if let Some(s) = pstats {
-                        format!("{:4}", s.wins.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "P.L" => {
-                    if let Some(s) = pstats {
-                        format!("{:4}", s.losses.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "P.CG" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.complete_games.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.SHO" => {
-                    if let Some(s) = pstats {
-                        format!("{:6}", s.shutouts.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "P.SV" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.saves.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.OUT" => {
-                    if let Some(s) = pstats {
-                        format!("{:6}", s.outs.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "P.H" => {
-                    if let Some(s) = pstats {
-                        format!("{:4}", s.hits.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "P.ER" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.earned_runs.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.HR" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.home_runs.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.BB" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.walks.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.IBB" => {
-                    if let Some(s) = pstats {
-                        format!("{:6}", s.intentional_walks.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "P.HBP" => {
-                    if let Some(s) = pstats {
-                        format!("{:6}", s.hit_batters.to_string())
-                    } else {
-                        format!("{:6}", "")
-                    }
-                }
-                "P.K" => {
-                    if let Some(s) = pstats {
-                        format!("{:4}", s.strikeouts.to_string())
-                    } else {
-                        format!("{:4}", "")
-                    }
-                }
-                "P.SB" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.stolen_bases_allowed.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                "P.GIDP" => {
-                    if let Some(s) = pstats {
-                        format!("{:7}", s.batters_grounded_into_double_plays.to_string())
-                    } else {
-                        format!("{:7}", "")
-                    }
-                }
-                "P.TB" => {
-                    if let Some(s) = pstats {
-                        format!("{:5}", s.total_bases_allowed.to_string())
-                    } else {
-                        format!("{:5}", "")
-                    }
-                }
-                _ => "".to_string(),
-            })
-            .collect::<Vec<_>>()
-            .join("")
+        s.get_header_items_for_pitcher()
     }
+    .into_iter()
+    .filter(|h| h != "Player" && h != "Team" && h != "Pos")
+    .collect();
+
+    let mut columns = vec![
+        Column::new("Split", 12),
+        Column::new("G", 5).right(),
+        Column::new("Pts/G", 9).right(),
+    ];
+    columns.extend(get_stat_columns(&header_items));
+    let rows: Vec<Vec<String>> = splits
+        .iter()
+        .map(|(split, games, fp)| {
+            let mut row = vec![
+                split.to_string(),
+                games.to_string(),
+                format!("{:.2}", fp.fantasy_points / *games as f32),
+            ];
+            row.extend(get_stat_cells(fp, &header_items));
+            row
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
 }
 
-pub fn print_scores_per_team(players: Vec<FantasyPlayer>, is_csv: bool) {
+fn get_per_game_cells(total: &FantasyPlayer, games: usize, headers: &[String]) -> Vec<String> {
+    headers
+        .iter()
+        .zip(get_stat_cells(total, headers))
+        .map(|(h, c)| match c.parse::<f32>() {
+            Ok(_) if h == "FanPts" => format!("{:.2}", total.fantasy_points / games as f32),
+            Ok(v) if h != "Pos" => format!("{:.2}", v / games as f32),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Returns the sum of fantasy points of rostered players per team, sorted by the points.
+pub fn get_scores_per_team(players: &[FantasyPlayer]) -> Vec<(String, f32)> {
     let mut scores = players
         .iter()
-        .filter(|x| x.team != Rc::new(Cow::Borrowed("<FA>")))
+        .filter(|x| *x.team != stats::FA)
         .fold(HashMap::new(), |mut acc, x| {
-            let fp = acc.entry(x.team.clone()).or_insert(0.0);
+            let fp = acc.entry(x.team.to_string()).or_insert(0.0);
             *fp += x.fantasy_points;
             acc
         })
         .into_iter()
-        .map(|(k, v)| (k, v))
         .collect::<Vec<_>>();
     scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    println!("# Team Rankings");
-    if is_csv {
-        println!("Team,FanPts");
-        scores.into_iter().for_each(|(team, pts)| {
-            println!("{},{}", team, pts);
-        });
-    } else {
-        println!("{:20}{:>8}", "Team", "FanPts");
-        scores.into_iter().for_each(|(team, pts)| {
-            println!("{:20}{:8.1}", team, pts);
-        });
-    }
+    scores
+}
+
+pub fn print_scores_per_team(players: Vec<FantasyPlayer>, f: &dyn Formatter) {
+    let rows: Vec<Vec<String>> = get_scores_per_team(&players)
+        .into_iter()
+        .map(|(team, pts)| vec![team, format!("{:.2}", pts)])
+        .collect();
+    println!("{}", f.heading(1, "Team Rankings"));
+    println!(
+        "{}",
+        f.table(
+            &[
+                Column::new("Team", 20),
+                Column::new("FanPts", 9).right().points()
+            ],
+            &rows
+        )
+    );
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::stats::format::{Csv, Pretty};
     use crate::stats::{self, BatterStats, FantasyPlayer, PitcherStats, Player};
+    use std::borrow::Cow;
+    use std::rc::Rc;
 
     fn mock_batter<'a>() -> Player<'a> {
         Player::new_batter(
@@ -706,28 +405,37 @@ mod test {
     }
 
     #[test]
-    fn get_header_string_should_return_csv_or_pretty_format_string() {
+    fn get_stat_columns_should_return_csv_or_pretty_header() {
         let headers: Vec<_> = vec!["Player", "Team", "FanPts", "Pos", "B.HR", "P.K"]
             .into_iter()
             .map(std::string::ToString::to_string)
             .collect();
 
-        let csv = get_header_string(&headers, true);
+        let csv = Csv.table(&get_stat_columns(&headers), &[]);
         assert_eq!("Player,Team,FanPts,Pos,B.HR,P.K", csv);
 
-        let pretty = get_header_string(&headers, false);
-        assert_eq!("Player            Team      FanPts Pos B.HR P.K ", pretty);
+        let pretty = Pretty { color: false }.table(&get_stat_columns(&headers), &[]);
+        assert_eq!("Player            Team       FanPts Pos B.HR P.K ", pretty);
     }
 
     #[test]
-    fn fantasy_player_get_stats_string_should_return_string() {
+    fn get_stat_cells_should_return_csv_or_pretty_row() {
         use crate::league::scoring::sample_scoring_rule;
-        use std::borrow::Cow;
-        use std::rc::Rc;
 
         let sr = sample_scoring_rule();
 
         let header_items = sr.get_header_items();
+        let pretty_row = |fp: &FantasyPlayer| {
+            Pretty { color: false }
+                .table(
+                    &get_stat_columns(&header_items),
+                    &[get_stat_cells(fp, &header_items)],
+                )
+                .lines()
+                .nth(1)
+                .unwrap()
+                .to_string()
+        };
 
         let batter = mock_batter();
         let fp = FantasyPlayer {
@@ -738,11 +446,11 @@ mod test {
 
         assert_eq!(
             "Trey Mancini,Avengers,13.50,RF,2,3,1,2,0,,,,,",
-            get_player_stats_string(&fp, &header_items, true)
+            get_stat_cells(&fp, &header_items).join(",")
         );
         assert_eq!(
-            "Trey Mancini      Avengers   13.50 RF  2   3   1    2     0                             ",
-            get_player_stats_string(&fp, &header_items, false)
+            "Trey Mancini      Avengers    13.50 RF    2   3    1     2    0                          ",
+            pretty_row(&fp)
         );
 
         let pitcher = mock_pitcher();
@@ -754,11 +462,32 @@ mod test {
 
         assert_eq!(
             "Blake Snell,Avengers,32.50,SP,,,,,,6,1,0,1,11",
-            get_player_stats_string(&fp, &header_items, true)
+            get_stat_cells(&fp, &header_items).join(",")
+        );
+        assert_eq!(
+            "Blake Snell       Avengers    32.50 SP                               6   1    0    1  11 ",
+            pretty_row(&fp)
         );
+    }
+
+    #[test]
+    fn get_per_game_cells_should_return_averages_of_stats() {
+        let mut fp = FantasyPlayer {
+            team: Rc::new(Cow::Borrowed("Avengers")),
+            player: mock_batter(),
+            fantasy_points: 13.5,
+        };
+        fp.add_stats(fp.clone());
+        let headers = vec![
+            "FanPts".to_string(),
+            "Pos".to_string(),
+            "B.R".to_string(),
+            "B.HR".to_string(),
+        ];
+
         assert_eq!(
-            "Blake Snell       Avengers   32.50 SP                          6      1   0    1    11  ",
-            get_player_stats_string(&fp, &header_items, false)
+            vec!["6.75", "RF", "1.00", "0.50"],
+            get_per_game_cells(&fp, 4, &headers)
         );
     }
 }
@@ -0,0 +1,252 @@
+use crate::league::{roster, scoring};
+use crate::stats::allowed;
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use log::warn;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+
+/// Totals of the games played in a park.
+#[derive(Debug, Default, Clone)]
+struct Park {
+    name: String,
+    games: usize,
+    runs: f32,
+    home_runs: f32,
+    batter_points: f32,
+    pitcher_points: f32,
+}
+impl Park {
+    fn per_game(&self, total: f32) -> f32 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        total / self.games as f32
+    }
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let min_games = matches.value_of("min_games").unwrap().parse::<usize>()?;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let per_date =
+        allowed::fantasy_players_per_date(&utils::date_strs(&config.date, range), &config, &s, &r);
+
+    let without_venue = per_date
+        .iter()
+        .filter(|(_, fps)| fps.iter().any(|fp| fp.player.venue.is_empty()))
+        .count();
+    if without_venue > 0 {
+        warn!(
+            "{} dates cached without venue are left out, run `mlbh2h refresh` to refetch them",
+            without_venue
+        );
+    }
+
+    let parks = parks(&per_date);
+    let league_avg = league_average(&parks);
+    let mut factors: Vec<(Park, [f32; 4])> = parks
+        .into_iter()
+        .filter(|p| p.games >= min_games)
+        .map(|p| {
+            let f = factors(&p, &league_avg);
+            (p, f)
+        })
+        .collect();
+    factors.sort_by(|a, b| b.1[2].partial_cmp(&a.1[2]).unwrap());
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Park Factors ({}, until {}, 100 is the league average)",
+                range, config.date
+            )
+        )
+    );
+    print_factors(&factors, f.as_ref());
+
+    Ok(())
+}
+
+/// Sums the runs, home runs and fantasy points of batters and pitchers by park.
+/// Games of a doubleheader are counted as a game, and players cached without venue are left out.
+fn parks(per_date: &[(String, Vec<FantasyPlayer>)]) -> Vec<Park> {
+    let mut parks: HashMap<String, Park> = HashMap::new();
+    let mut games: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+    for (date, fps) in per_date.iter() {
+        fps.iter()
+            .filter(|fp| !fp.player.venue.is_empty())
+            .for_each(|fp| {
+                let venue = fp.player.venue.to_string();
+                let park = parks.entry(venue.clone()).or_insert_with(|| Park {
+                    name: venue.clone(),
+                    ..Default::default()
+                });
+                match (&fp.player.batter_stats, &fp.player.pitcher_stats) {
+                    (_, Some(_)) => park.pitcher_points += fp.fantasy_points,
+                    (Some(bs), None) => {
+                        park.runs += bs.runs as f32;
+                        park.home_runs += bs.home_runs as f32;
+                        park.batter_points += fp.fantasy_points;
+                    }
+                    (None, None) => {}
+                }
+                games.entry(venue).or_default().insert(date.clone());
+            });
+    }
+
+    parks
+        .into_values()
+        .map(|mut p| {
+            p.games = games.get(&p.name).map(|g| g.len()).unwrap_or(0);
+            p
+        })
+        .collect()
+}
+
+/// Totals of all parks, i.e. the league average per game.
+fn league_average(parks: &[Park]) -> Park {
+    parks.iter().fold(
+        Park {
+            name: "League".to_string(),
+            ..Default::default()
+        },
+        |mut acc, p| {
+            acc.games += p.games;
+            acc.runs += p.runs;
+            acc.home_runs += p.home_runs;
+            acc.batter_points += p.batter_points;
+            acc.pitcher_points += p.pitcher_points;
+            acc
+        },
+    )
+}
+
+/// Returns the factors of runs, home runs, batter points and pitcher points per game,
+/// relative to the league average (100).
+fn factors(park: &Park, league_avg: &Park) -> [f32; 4] {
+    let factor = |f: fn(&Park) -> f32| {
+        let avg = league_avg.per_game(f(league_avg));
+        if avg == 0.0 {
+            return 0.0;
+        }
+        park.per_game(f(park)) / avg * 100.0
+    };
+    [
+        factor(|p| p.runs),
+        factor(|p| p.home_runs),
+        factor(|p| p.batter_points),
+        factor(|p| p.pitcher_points),
+    ]
+}
+
+fn print_factors(factors: &[(Park, [f32; 4])], f: &dyn Formatter) {
+    if factors.is_empty() {
+        println!(
+            "No stats with venues found in the stats cache, run `mlbh2h refresh` to refetch them."
+        );
+        return;
+    }
+
+    let columns = [
+        Column::new("Park", 32).truncated(),
+        Column::new("G", 5).right(),
+        Column::new("R/G", 7).right(),
+        Column::new("RIdx", 7).right(),
+        Column::new("HR/G", 7).right(),
+        Column::new("HRIdx", 7).right(),
+        Column::new("BatPts/G", 10).right(),
+        Column::new("BatIdx", 7).right(),
+        Column::new("PitPts/G", 10).right(),
+        Column::new("PitIdx", 7).right(),
+    ];
+    let rows: Vec<Vec<String>> = factors
+        .iter()
+        .map(|(p, idx)| {
+            vec![
+                p.name.clone(),
+                p.games.to_string(),
+                format!("{:.2}", p.per_game(p.runs)),
+                format!("{:.0}", idx[0]),
+                format!("{:.2}", p.per_game(p.home_runs)),
+                format!("{:.0}", idx[1]),
+                format!("{:.2}", p.per_game(p.batter_points)),
+                format!("{:.0}", idx[2]),
+                format!("{:.2}", p.per_game(p.pitcher_points)),
+                format!("{:.0}", idx[3]),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, Player};
+    use crate::utils::assert_eq_f32;
+    use std::borrow::Cow;
+
+    #[test]
+    fn factors_should_compare_parks_with_league_average() {
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+        let batter = |runs: u32, home_runs: u32, venue: &'static str| {
+            let mut p = Player::new_batter(
+                "Cody Bellinger",
+                "OF",
+                "RF",
+                BatterStats {
+                    runs,
+                    home_runs,
+                    ..Default::default()
+                },
+            );
+            p.venue = Cow::Borrowed(venue);
+            stats::to_fantasy_player(&p, &s, &r)
+        };
+        let per_date = vec![
+            (
+                "2019-07-01".to_string(),
+                vec![batter(3, 2, "Coors Field"), batter(1, 0, "Oracle Park")],
+            ),
+            (
+                "2019-07-02".to_string(),
+                vec![
+                    batter(2, 1, "Coors Field"),
+                    batter(1, 1, "Coors Field"),
+                    batter(1, 1, ""),
+                ],
+            ),
+        ];
+
+        let parks = parks(&per_date);
+        let league_avg = league_average(&parks);
+        assert_eq!(3, league_avg.games);
+        assert_eq_f32(7.0, league_avg.runs);
+
+        let coors = parks.iter().find(|p| p.name == "Coors Field").unwrap();
+        assert_eq!(2, coors.games);
+        let f = factors(coors, &league_avg);
+        assert_eq_f32(128.5714, f[0]);
+        assert_eq_f32(150.0, f[1]);
+    }
+}
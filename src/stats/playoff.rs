@@ -0,0 +1,489 @@
+use crate::league::matchup::{self, PlayoffFormat, Week};
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, Config};
+use crate::utils;
+
+use clap::ArgMatches;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct NoCompletedWeek(String);
+impl fmt::Display for NoCompletedWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no matchup week is completed by {}, weekly fantasy points cannot be sampled",
+            self.0
+        )
+    }
+}
+impl Error for NoCompletedWeek {}
+
+#[derive(Debug, Clone)]
+struct InvalidSimulations(String);
+impl fmt::Display for InvalidSimulations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid number of simulations {}, should be 1 or more",
+            self.0
+        )
+    }
+}
+impl Error for InvalidSimulations {}
+
+#[derive(Debug, Default, Clone)]
+struct Standing {
+    team: String,
+    wins: u32,
+    losses: u32,
+    ties: u32,
+    points_for: f32,
+}
+impl Standing {
+    fn new(team: &str) -> Standing {
+        Standing {
+            team: team.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn win_pct(&self) -> f32 {
+        let games = self.wins + self.losses + self.ties;
+        if games == 0 {
+            return 0.0;
+        }
+        (self.wins as f32 + self.ties as f32 / 2.0) / games as f32
+    }
+
+    fn record(&mut self, points: f32, opponent_points: f32) {
+        if points > opponent_points {
+            self.wins += 1;
+        } else if points < opponent_points {
+            self.losses += 1;
+        } else {
+            self.ties += 1;
+        }
+        self.points_for += points;
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Odds {
+    playoff: u32,
+    bye: u32,
+    championship: u32,
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let simulations = parse_simulations(matches.value_of("simulations").unwrap())?;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+    let schedule = matchup::load(&league, &r)?;
+    schedule.playoff.rounds()?;
+
+    let teams = matchup::team_names(&r);
+    let mut standings: Vec<Standing> = teams.iter().map(|t| Standing::new(t)).collect();
+    let mut history: HashMap<String, Vec<f32>> = HashMap::new();
+    let mut remaining: Vec<&Week> = vec![];
+
+    for week in schedule.weeks.iter() {
+        if week.end.as_str() > config.date.as_ref() {
+            remaining.push(week);
+            continue;
+        }
+
+        let points = weekly_points(week, &config, &s, &r);
+        teams.iter().for_each(|t| {
+            let pts = points.get(t).cloned().unwrap_or(0.0);
+            history.entry(t.clone()).or_default().push(pts);
+        });
+        record_week(&mut standings, week, |t| {
+            points.get(t).cloned().unwrap_or(0.0)
+        });
+    }
+
+    if history.is_empty() {
+        return Err(Box::new(NoCompletedWeek(config.date.to_string())));
+    }
+
+    let mut rng = rand::thread_rng();
+    let odds = simulate(
+        &standings,
+        &remaining,
+        &history,
+        schedule.playoff,
+        simulations,
+        &mut rng,
+    );
+
+    let seeds = seeded(&standings);
+    println!();
+    print_odds(
+        &seeds,
+        &odds,
+        schedule.playoff,
+        simulations,
+        f.as_ref(),
+        &config,
+    );
+    println!();
+    print_bracket(&seeds, schedule.playoff, f.as_ref());
+
+    Ok(())
+}
+
+fn weekly_points(
+    week: &Week,
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> HashMap<String, f32> {
+    utils::weekly_date_strs(&week.end)
+        .iter()
+        .map(|d| stats::fantasy_points_per_team(d, config, s, r))
+        .fold(HashMap::new(), |mut acc, pts| {
+            pts.into_iter().for_each(|(team, p)| {
+                *acc.entry(team).or_insert(0.0) += p;
+            });
+            acc
+        })
+}
+
+fn record_week<F>(standings: &mut [Standing], week: &Week, mut points_of: F)
+where
+    F: FnMut(&str) -> f32,
+{
+    week.matchups.iter().for_each(|m| {
+        let home_pts = points_of(&m.home);
+        let away_pts = points_of(&m.away);
+        if let Some(st) = standings.iter_mut().find(|st| st.team == m.home) {
+            st.record(home_pts, away_pts);
+        }
+        if let Some(st) = standings.iter_mut().find(|st| st.team == m.away) {
+            st.record(away_pts, home_pts);
+        }
+    });
+}
+
+/// Returns standings ordered by seed: win percentage first, then points for.
+fn seeded(standings: &[Standing]) -> Vec<Standing> {
+    let mut seeds = standings.to_vec();
+    seeds.sort_by(|a, b| {
+        b.win_pct()
+            .partial_cmp(&a.win_pct())
+            .unwrap()
+            .then(b.points_for.partial_cmp(&a.points_for).unwrap())
+    });
+    seeds
+}
+
+/// Plays out a single elimination bracket and returns the champion.
+/// The higher seed advances when a game is tied.
+fn play_bracket<F>(seeds: &[String], playoff: PlayoffFormat, mut points_of: F) -> String
+where
+    F: FnMut(&str) -> f32,
+{
+    let mut play = |high: usize, low: usize| {
+        if points_of(&seeds[low]) > points_of(&seeds[high]) {
+            low
+        } else {
+            high
+        }
+    };
+
+    let first_round: Vec<usize> = (playoff.byes..playoff.teams).collect();
+    let n = first_round.len();
+    let mut alive: Vec<usize> = (0..playoff.byes).collect();
+    (0..n / 2).for_each(|i| alive.push(play(first_round[i], first_round[n - 1 - i])));
+
+    while alive.len() > 1 {
+        alive.sort();
+        let n = alive.len();
+        alive = (0..n / 2)
+            .map(|i| play(alive[i], alive[n - 1 - i]))
+            .collect();
+    }
+
+    seeds[alive[0]].clone()
+}
+
+fn simulate<R: Rng>(
+    standings: &[Standing],
+    remaining: &[&Week],
+    history: &HashMap<String, Vec<f32>>,
+    playoff: PlayoffFormat,
+    simulations: usize,
+    rng: &mut R,
+) -> HashMap<String, Odds> {
+    let mut odds: HashMap<String, Odds> = HashMap::new();
+    let mut sample = |team: &str| -> f32 {
+        history
+            .get(team)
+            .and_then(|pts| pts.choose(rng))
+            .cloned()
+            .unwrap_or(0.0)
+    };
+
+    for _ in 0..simulations {
+        let mut season = standings.to_vec();
+        remaining
+            .iter()
+            .for_each(|w| record_week(&mut season, w, &mut sample));
+
+        let seeds: Vec<String> = seeded(&season)
+            .into_iter()
+            .take(playoff.teams)
+            .map(|st| st.team)
+            .collect();
+        seeds.iter().enumerate().for_each(|(i, t)| {
+            let o = odds.entry(t.clone()).or_default();
+            o.playoff += 1;
+            if i < playoff.byes {
+                o.bye += 1;
+            }
+        });
+
+        let champion = play_bracket(&seeds, playoff, &mut sample);
+        odds.entry(champion).or_default().championship += 1;
+    }
+
+    odds
+}
+
+fn print_odds(
+    seeds: &[Standing],
+    odds: &HashMap<String, Odds>,
+    playoff: PlayoffFormat,
+    simulations: usize,
+    f: &dyn Formatter,
+    config: &Config,
+) {
+    let pct = |n: u32| format!("{:.1}", n as f32 * 100.0 / simulations as f32);
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Playoff Odds ({}, {} simulations, {} teams, {} byes)",
+                config.date, simulations, playoff.teams, playoff.byes
+            )
+        )
+    );
+
+    let columns = vec![
+        Column::new("Team", 20),
+        Column::new("W", 4).right(),
+        Column::new("L", 4).right(),
+        Column::new("T", 4).right(),
+        Column::new("PF", 9).right(),
+        Column::new("Playoff%", 9).right(),
+        Column::new("Bye%", 7).right(),
+        Column::new("Champ%", 7).right(),
+    ];
+    let empty = Odds::default();
+    let rows: Vec<Vec<String>> = seeds
+        .iter()
+        .map(|st| {
+            let o = odds.get(&st.team).unwrap_or(&empty);
+            vec![
+                st.team.clone(),
+                st.wins.to_string(),
+                st.losses.to_string(),
+                st.ties.to_string(),
+                format!("{:.1}", st.points_for),
+                pct(o.playoff),
+                pct(o.bye),
+                pct(o.championship),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_bracket(seeds: &[Standing], playoff: PlayoffFormat, f: &dyn Formatter) {
+    println!("{}", f.heading(1, "Playoff Bracket (by current standings)"));
+    let columns = vec![
+        Column::new("Round", 9),
+        Column::new("Seed", 5).right(),
+        Column::new("Team", 20),
+        Column::new("Opponent", 20),
+    ];
+    println!("{}", f.table(&columns, &bracket_rows(seeds, playoff)));
+}
+
+/// Returns the games of the first round by seed, and a row per later round as they are reseeded.
+fn bracket_rows(seeds: &[Standing], playoff: PlayoffFormat) -> Vec<Vec<String>> {
+    let rounds = playoff.rounds().unwrap_or(0);
+    let round_name = |r: usize| {
+        if r == rounds {
+            "Final".to_string()
+        } else {
+            format!("Round {}", r)
+        }
+    };
+    let game = |i: usize, opponent: String| {
+        vec![
+            round_name(1),
+            (i + 1).to_string(),
+            seeds[i].team.clone(),
+            opponent,
+        ]
+    };
+
+    let mut rows: Vec<Vec<String>> = (0..playoff.byes)
+        .map(|i| game(i, "bye".to_string()))
+        .collect();
+    let n = playoff.teams - playoff.byes;
+    (0..n / 2).for_each(|i| {
+        let opponent = playoff.teams - 1 - i;
+        rows.push(game(
+            playoff.byes + i,
+            format!("({}) {}", opponent + 1, seeds[opponent].team),
+        ));
+    });
+
+    let mut alive = playoff.byes + n / 2;
+    (2..=rounds).for_each(|r| {
+        rows.push(vec![
+            round_name(r),
+            "".to_string(),
+            format!("{} teams left", alive),
+            "reseeded (highest vs lowest)".to_string(),
+        ]);
+        alive /= 2;
+    });
+
+    rows
+}
+
+fn parse_simulations(s: &str) -> Result<usize, InvalidSimulations> {
+    match s.parse::<usize>() {
+        Ok(n) if n >= 1 => Ok(n),
+        _ => Err(InvalidSimulations(s.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn standing(team: &str, wins: u32, losses: u32, points_for: f32) -> Standing {
+        Standing {
+            team: team.to_string(),
+            wins,
+            losses,
+            ties: 0,
+            points_for,
+        }
+    }
+
+    #[test]
+    fn seeded_should_order_by_win_pct_then_points_for() {
+        let standings = vec![
+            standing("A", 1, 2, 300.0),
+            standing("B", 2, 1, 250.0),
+            standing("C", 2, 1, 280.0),
+            standing("D", 0, 3, 400.0),
+        ];
+
+        let seeds: Vec<_> = seeded(&standings).into_iter().map(|st| st.team).collect();
+        assert_eq!(vec!["C", "B", "A", "D"], seeds);
+    }
+
+    #[test]
+    fn play_bracket_should_give_byes_to_top_seeds() {
+        let seeds: Vec<String> = vec!["A", "B", "C", "D", "E", "F"]
+            .into_iter()
+            .map(std::string::ToString::to_string)
+            .collect();
+        let playoff = PlayoffFormat { teams: 6, byes: 2 };
+
+        // the lowest seed always scores the most, so it wins every game it plays
+        let champion = play_bracket(&seeds, playoff, |t| match t {
+            "F" => 100.0,
+            "A" => 90.0,
+            _ => 10.0,
+        });
+        assert_eq!("F", champion);
+
+        // ties are broken by the seed
+        let champion = play_bracket(&seeds, playoff, |_| 10.0);
+        assert_eq!("A", champion);
+    }
+
+    #[test]
+    fn simulate_should_count_playoff_bye_and_championship() {
+        let standings = vec![
+            standing("A", 3, 0, 300.0),
+            standing("B", 2, 1, 200.0),
+            standing("C", 1, 2, 100.0),
+            standing("D", 0, 3, 50.0),
+        ];
+        let history: HashMap<String, Vec<f32>> = vec![
+            ("A".to_string(), vec![100.0]),
+            ("B".to_string(), vec![70.0]),
+            ("C".to_string(), vec![30.0]),
+            ("D".to_string(), vec![10.0]),
+        ]
+        .into_iter()
+        .collect();
+        let playoff = PlayoffFormat { teams: 2, byes: 0 };
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let odds = simulate(&standings, &[], &history, playoff, 100, &mut rng);
+
+        assert_eq!(100, odds["A"].playoff);
+        assert_eq!(100, odds["A"].championship);
+        assert_eq!(100, odds["B"].playoff);
+        assert_eq!(0, odds["B"].championship);
+        assert!(!odds.contains_key("C"));
+    }
+
+    #[test]
+    fn bracket_rows_should_have_first_round_games_and_later_rounds() {
+        let standings: Vec<_> = vec!["A", "B", "C", "D", "E", "F"]
+            .into_iter()
+            .map(|t| standing(t, 0, 0, 0.0))
+            .collect();
+
+        let rows = bracket_rows(&standings, PlayoffFormat { teams: 6, byes: 2 });
+        assert_eq!(
+            vec![
+                vec!["Round 1", "1", "A", "bye"],
+                vec!["Round 1", "2", "B", "bye"],
+                vec!["Round 1", "3", "C", "(6) F"],
+                vec!["Round 1", "4", "D", "(5) E"],
+                vec![
+                    "Round 2",
+                    "",
+                    "4 teams left",
+                    "reseeded (highest vs lowest)"
+                ],
+                vec!["Final", "", "2 teams left", "reseeded (highest vs lowest)"],
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn parse_simulations_should_reject_less_than_one() {
+        assert_eq!(1000, parse_simulations("1000").unwrap());
+        assert!(parse_simulations("0").is_err());
+        assert!(parse_simulations("-1").is_err());
+        assert!(parse_simulations("a").is_err());
+    }
+}
@@ -0,0 +1,504 @@
+use crate::league::matchup::{self, Matchup};
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, schedule, Config};
+use crate::utils;
+
+use log::info;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+/// Fantasy points per game of a rostered player in the recent form window.
+#[derive(Debug, Default, Clone)]
+struct Form {
+    name: String,
+    team: String,
+    pitcher: bool,
+    primary_position: String,
+    mlb_team: String,
+    points: Vec<f32>,
+}
+impl Form {
+    fn key(&self) -> String {
+        stats::player_key(&self.name, self.pitcher)
+    }
+
+    fn per_game(&self) -> f32 {
+        if self.points.is_empty() {
+            return 0.0;
+        }
+        self.points.iter().sum::<f32>() / self.points.len() as f32
+    }
+
+    /// Ratio of the games played by the player to the games played by his MLB team.
+    fn appearance_rate(&self, team_games: &HashMap<String, usize>, days: usize) -> f32 {
+        let games = match team_games.get(&self.mlb_team) {
+            Some(&g) if g > 0 => g,
+            _ => days,
+        };
+        if games == 0 {
+            return 0.0;
+        }
+        (self.points.len() as f32 / games as f32).min(1.0)
+    }
+}
+
+/// Games left for each MLB team and probable starts for each pitcher.
+#[derive(Debug, Default)]
+struct RemainingGames {
+    games: HashMap<String, usize>,
+    starts: HashMap<String, usize>,
+}
+
+const SIMULATIONS: usize = 10000;
+
+/// `opportunities` are the probable starts for starting pitchers and the games left for
+/// the MLB team for others, each of which the player plays with the probability of `rate`.
+#[derive(Debug, Default, Clone)]
+struct Projection {
+    form: Form,
+    actual: f32,
+    opportunities: usize,
+    rate: f32,
+    remaining: f32,
+}
+impl Projection {
+    fn games(&self) -> f32 {
+        self.opportunities as f32 * self.rate
+    }
+}
+
+pub fn show(
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> Result<(), Box<dyn Error>> {
+    let form_range = if config.range == "1d" {
+        "2w"
+    } else {
+        config.range.as_ref()
+    };
+    let form_dates = utils::date_strs(&config.date, form_range);
+    let remaining_dates = utils::remaining_week_date_strs(&config.date);
+
+    let (forms, team_games, days) = recent_forms(&form_dates, config, s, r);
+    let remaining = remaining_games(&remaining_dates, config)?;
+    let actual = weekly_actual_points(&utils::weekly_date_strs(&config.date), config, s, r);
+
+    let mut projections: Vec<Projection> = forms
+        .into_iter()
+        .map(|f| {
+            let (opportunities, rate) = opportunities(&f, &remaining, &team_games, days);
+            Projection {
+                actual: actual.get(&f.key()).cloned().unwrap_or(0.0),
+                remaining: f.per_game() * opportunities as f32 * rate,
+                opportunities,
+                rate,
+                form: f,
+            }
+        })
+        .collect();
+    projections.sort_by(|a, b| {
+        a.form.team.cmp(&b.form.team).then(
+            (b.actual + b.remaining)
+                .partial_cmp(&(a.actual + a.remaining))
+                .unwrap(),
+        )
+    });
+
+    let f = format::formatter(&config.format)?;
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Rest-of-week Projections ({} days left, recent form: {})",
+                remaining_dates.len(),
+                form_range
+            )
+        )
+    );
+    print_player_projections(&projections, f.as_ref());
+    println!();
+    print_team_projections(&projections, f.as_ref());
+
+    let schedule = matchup::load(&config.league, r)?;
+    match schedule.week_of(&config.date) {
+        Some(week) => {
+            let mut rng = rand::thread_rng();
+            let odds = win_probabilities(&projections, &week.matchups, SIMULATIONS, &mut rng);
+            println!();
+            print_win_probabilities(&week.start, &odds, f.as_ref());
+        }
+        None => info!("No regular season matchup on {}", config.date),
+    }
+
+    Ok(())
+}
+
+/// Collects the recent form of every rostered player, the number of days each MLB team
+/// played and the number of days with cached stats in given dates.
+fn recent_forms(
+    dates: &[String],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> (Vec<Form>, HashMap<String, usize>, usize) {
+    let mut forms: Vec<Form> = r
+        .players
+        .iter()
+        .map(|p| Form {
+            name: p.name.to_string(),
+            team: p.team.to_string(),
+            pitcher: p.is_pitcher(),
+            ..Default::default()
+        })
+        .collect();
+    let mut team_games: HashMap<String, usize> = HashMap::new();
+    let mut days = 0;
+
+    for d in dates.iter() {
+        let players = stats::players_for_date(d.clone(), config);
+        if players.is_empty() {
+            continue;
+        }
+        days += 1;
+
+        let mlb_teams: HashSet<_> = players
+            .iter()
+            .filter(|p| !p.mlb_team.is_empty())
+            .map(|p| p.mlb_team.to_string())
+            .collect();
+        mlb_teams
+            .into_iter()
+            .for_each(|t| *team_games.entry(t).or_insert(0) += 1);
+
+        let fplayers = stats::create_fantasy_players(&players, s, r, false).unwrap();
+        fplayers.into_iter().for_each(|fp| {
+            let key = fp.key();
+            if let Some(f) = forms.iter_mut().find(|f| f.key() == key) {
+                f.points.push(fp.fantasy_points);
+                f.primary_position = fp.player.primary_position.to_string();
+                if !fp.player.mlb_team.is_empty() {
+                    f.mlb_team = fp.player.mlb_team.to_string();
+                }
+            }
+        });
+    }
+
+    (forms, team_games, days)
+}
+
+fn remaining_games(dates: &[String], config: &Config) -> Result<RemainingGames, Box<dyn Error>> {
+    let mut remaining = RemainingGames::default();
+
+    for d in dates.iter() {
+        thread::sleep(Duration::from_millis(1050));
+        let games = schedule::get_games_with_probables(config, d)?;
+        info!("{} games scheduled on {}", games.len(), d);
+
+        for g in games.iter() {
+            *remaining.games.entry(g.home.abbr.clone()).or_insert(0) += 1;
+            *remaining.games.entry(g.away.abbr.clone()).or_insert(0) += 1;
+
+            g.probable_starters().into_iter().for_each(|(_, name, _)| {
+                *remaining.starts.entry(name.to_lowercase()).or_insert(0) += 1;
+            });
+        }
+    }
+
+    Ok(remaining)
+}
+
+/// Starting pitchers are projected by their probable starts,
+/// others by the games left for their MLB teams.
+fn opportunities(
+    f: &Form,
+    remaining: &RemainingGames,
+    team_games: &HashMap<String, usize>,
+    days: usize,
+) -> (usize, f32) {
+    if f.pitcher && f.primary_position == "SP" {
+        let starts = remaining
+            .starts
+            .get(&f.name.to_lowercase())
+            .cloned()
+            .unwrap_or(0);
+        return (starts, 1.0);
+    }
+
+    let games = remaining.games.get(&f.mlb_team).cloned().unwrap_or(0);
+    (games, f.appearance_rate(team_games, days))
+}
+
+/// Simulates the rest of the week by drawing each game from the player's recent games.
+fn simulate_team_points<R: Rng>(projections: &[Projection], rng: &mut R) -> HashMap<String, f32> {
+    projections.iter().fold(HashMap::new(), |mut acc, p| {
+        let mut pts = p.actual;
+        for _ in 0..p.opportunities {
+            if rng.gen::<f32>() < p.rate {
+                pts += p.form.points.choose(rng).cloned().unwrap_or(0.0);
+            }
+        }
+        *acc.entry(p.form.team.clone()).or_insert(0.0) += pts;
+        acc
+    })
+}
+
+/// Returns (matchup, average points of home, average points of away, home win probability)
+/// for each matchup. A tied simulation counts as half a win for both teams.
+fn win_probabilities<R: Rng>(
+    projections: &[Projection],
+    matchups: &[Matchup],
+    simulations: usize,
+    rng: &mut R,
+) -> Vec<(Matchup, f32, f32, f32)> {
+    let mut odds: Vec<(Matchup, f32, f32, f32)> = matchups
+        .iter()
+        .map(|m| (m.clone(), 0.0, 0.0, 0.0))
+        .collect();
+
+    for _ in 0..simulations {
+        let points = simulate_team_points(projections, rng);
+        odds.iter_mut()
+            .for_each(|(m, home_pts, away_pts, home_wins)| {
+                let home = points.get(&m.home).cloned().unwrap_or(0.0);
+                let away = points.get(&m.away).cloned().unwrap_or(0.0);
+                *home_pts += home;
+                *away_pts += away;
+                if home > away {
+                    *home_wins += 1.0;
+                } else if (home - away).abs() < f32::EPSILON {
+                    *home_wins += 0.5;
+                }
+            });
+    }
+
+    let n = simulations as f32;
+    odds.into_iter()
+        .map(|(m, home_pts, away_pts, home_wins)| (m, home_pts / n, away_pts / n, home_wins / n))
+        .collect()
+}
+
+fn weekly_actual_points(
+    dates: &[String],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> HashMap<String, f32> {
+    let players: Vec<_> = dates
+        .iter()
+        .flat_map(|d| stats::players_for_date(d.clone(), config))
+        .collect();
+    stats::create_fantasy_players(&players, s, r, false)
+        .unwrap()
+        .into_iter()
+        .map(|fp| (fp.key(), fp.fantasy_points))
+        .collect()
+}
+
+fn print_win_probabilities(week: &str, odds: &[(Matchup, f32, f32, f32)], f: &dyn Formatter) {
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Matchup Win Probability (week of {}, {} simulations)",
+                week, SIMULATIONS
+            )
+        )
+    );
+
+    let columns = vec![
+        Column::new("Team", 20),
+        Column::new("Proj", 8).right(),
+        Column::new("Win%", 8).right(),
+        Column::new("Opponent", 20),
+        Column::new("OppProj", 8).right(),
+        Column::new("OppWin%", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = odds
+        .iter()
+        .map(|(m, home_pts, away_pts, home_win)| {
+            vec![
+                m.home.clone(),
+                format!("{:.2}", home_pts),
+                format!("{:.1}", home_win * 100.0),
+                m.away.clone(),
+                format!("{:.2}", away_pts),
+                format!("{:.1}", (1.0 - home_win) * 100.0),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_player_projections(projections: &[Projection], f: &dyn Formatter) {
+    let columns = vec![
+        Column::new("Player", 18).truncated(),
+        Column::new("Team", 10).truncated(),
+        Column::new("Pos", 4),
+        Column::new("MLB", 5),
+        Column::new("G", 5).right(),
+        Column::new("Pts/G", 8).right(),
+        Column::new("Actual", 8).right(),
+        Column::new("Remain", 8).right(),
+        Column::new("Proj", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = projections
+        .iter()
+        .map(|p| {
+            let form = &p.form;
+            vec![
+                form.name.clone(),
+                form.team.clone(),
+                form.primary_position.clone(),
+                form.mlb_team.clone(),
+                format!("{:.1}", p.games()),
+                format!("{:.2}", form.per_game()),
+                format!("{:.2}", p.actual),
+                format!("{:.2}", p.remaining),
+                format!("{:.2}", p.actual + p.remaining),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_team_projections(projections: &[Projection], f: &dyn Formatter) {
+    let mut teams: Vec<(String, f32, f32)> = vec![];
+    projections.iter().for_each(
+        |p| match teams.iter_mut().find(|(t, _, _)| *t == p.form.team) {
+            Some(t) => {
+                t.1 += p.actual;
+                t.2 += p.remaining;
+            }
+            None => teams.push((p.form.team.clone(), p.actual, p.remaining)),
+        },
+    );
+    teams.sort_by(|a, b| (b.1 + b.2).partial_cmp(&(a.1 + a.2)).unwrap());
+
+    println!("{}", f.heading(1, "Projected Team Rankings"));
+    let columns = vec![
+        Column::new("Team", 20),
+        Column::new("Actual", 8).right(),
+        Column::new("Remain", 8).right(),
+        Column::new("Proj", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = teams
+        .into_iter()
+        .map(|(team, actual, remaining)| {
+            vec![
+                team,
+                format!("{:.2}", actual),
+                format!("{:.2}", remaining),
+                format!("{:.2}", actual + remaining),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::assert_eq_f32;
+
+    fn form(pitcher: bool, primary_position: &str, points: Vec<f32>) -> Form {
+        Form {
+            name: "Blake Snell".to_string(),
+            team: "LA Bulls".to_string(),
+            pitcher,
+            primary_position: primary_position.to_string(),
+            mlb_team: "TB".to_string(),
+            points,
+        }
+    }
+
+    #[test]
+    fn appearance_rate_should_use_games_of_mlb_team() {
+        let f = form(false, "RF", vec![1.0, 2.0, 3.0]);
+        let mut team_games = HashMap::new();
+        team_games.insert("TB".to_string(), 6);
+
+        assert_eq_f32(0.5, f.appearance_rate(&team_games, 14));
+        assert_eq_f32(3.0 / 14.0, f.appearance_rate(&HashMap::new(), 14));
+        assert_eq_f32(0.0, f.appearance_rate(&HashMap::new(), 0));
+    }
+
+    #[test]
+    fn opportunities_should_count_probable_starts_for_starting_pitchers() {
+        let mut remaining = RemainingGames::default();
+        remaining.games.insert("TB".to_string(), 6);
+        remaining.starts.insert("blake snell".to_string(), 2);
+        let mut team_games = HashMap::new();
+        team_games.insert("TB".to_string(), 12);
+
+        let sp = form(true, "SP", vec![20.0, 10.0]);
+        let (games, rate) = opportunities(&sp, &remaining, &team_games, 14);
+        assert_eq!(2, games);
+        assert_eq_f32(1.0, rate);
+
+        let rp = form(true, "RP", vec![3.0, 1.0, 2.0, 2.0]);
+        let (games, rate) = opportunities(&rp, &remaining, &team_games, 14);
+        assert_eq!(6, games);
+        assert_eq_f32(1.0 / 3.0, rate);
+
+        let batter = form(false, "RF", vec![1.0; 12]);
+        let (games, rate) = opportunities(&batter, &remaining, &team_games, 14);
+        assert_eq!(6, games);
+        assert_eq_f32(1.0, rate);
+    }
+
+    #[test]
+    fn win_probabilities_should_simulate_remaining_games() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let projection = |team: &str, actual: f32, opportunities: usize, points: Vec<f32>| {
+            let mut f = form(false, "RF", points);
+            f.team = team.to_string();
+            Projection {
+                form: f,
+                actual,
+                opportunities,
+                rate: 1.0,
+                remaining: 0.0,
+            }
+        };
+        let projections = vec![
+            projection("A", 100.0, 0, vec![]),
+            projection("B", 0.0, 1, vec![50.0]),
+            projection("C", 0.0, 2, vec![60.0]),
+            projection("D", 120.0, 0, vec![]),
+        ];
+        let matchups = vec![
+            Matchup {
+                home: "A".to_string(),
+                away: "B".to_string(),
+            },
+            Matchup {
+                home: "C".to_string(),
+                away: "D".to_string(),
+            },
+        ];
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let odds = win_probabilities(&projections, &matchups, 100, &mut rng);
+
+        assert_eq_f32(100.0, odds[0].1);
+        assert_eq_f32(50.0, odds[0].2);
+        assert_eq_f32(1.0, odds[0].3);
+        assert_eq_f32(0.5, odds[1].3);
+    }
+
+    #[test]
+    fn per_game_should_return_average_points() {
+        assert_eq_f32(15.0, form(true, "SP", vec![20.0, 10.0]).per_game());
+        assert_eq_f32(0.0, form(true, "SP", vec![]).per_game());
+    }
+}
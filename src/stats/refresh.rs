@@ -0,0 +1,107 @@
+use crate::stats::{self, sportradar};
+use crate::utils;
+
+use clap::ArgMatches;
+use log::warn;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+
+/// Per-game fields (MLB team, opponent, home/away, day/night and venue) of the cached players.
+const GAME_FIELDS: [&str; 5] = ["mlb_team", "opponent", "home", "day_night", "venue"];
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let dry_run = matches.is_present("dry_run");
+
+    let range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let dates: HashSet<String> = utils::date_strs(&config.date, range).into_iter().collect();
+
+    let mut stale_dates = vec![];
+    for (date, filepath) in stats::cached_stats_files() {
+        if !dates.contains(&date) {
+            continue;
+        }
+        match fs::read_to_string(&filepath)
+            .map_err(Box::from)
+            .and_then(|j| is_stale(&j))
+        {
+            Ok(false) => {}
+            Ok(true) => stale_dates.push((date, filepath)),
+            Err(e) => {
+                warn!("Refetching unreadable stats file {}: {}", filepath, e);
+                stale_dates.push((date, filepath));
+            }
+        }
+    }
+
+    if dry_run {
+        stale_dates
+            .iter()
+            .for_each(|(date, _)| println!("{}", date));
+        return Ok(());
+    }
+
+    let mut refreshed = 0;
+    for (date, filepath) in stale_dates.iter() {
+        let sr_players = match sportradar::get_players(&config, date) {
+            Ok(p) if !p.is_empty() => p,
+            Ok(_) => {
+                warn!("No stats fetched for {}, keeping the cached file", date);
+                continue;
+            }
+            Err(e) => {
+                warn!(
+                    "Fetching stats for {} failed, keeping the cached file: {}",
+                    date, e
+                );
+                continue;
+            }
+        };
+        let players = stats::convert_players(sr_players)?;
+        stats::write_players(filepath, &players)?;
+        refreshed += 1;
+    }
+    println!(
+        "Refreshed {} of {} stale cached dates.",
+        refreshed,
+        stale_dates.len()
+    );
+
+    Ok(())
+}
+
+/// Returns true if any of the cached players misses the per-game fields, i.e. the players were
+/// cached before the fields were added. Fields left empty by the API are not refetched.
+fn is_stale(json: &str) -> Result<bool, Box<dyn Error>> {
+    let players: Vec<Map<String, Value>> = serde_json::from_str(json)?;
+    Ok(players
+        .iter()
+        .any(|p| GAME_FIELDS.iter().any(|f| !p.contains_key(*f))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_stale_should_return_true_if_any_player_misses_game_fields() {
+        let old = fs::read_to_string("testdata/players_converted.json").unwrap();
+        assert!(is_stale(&old).unwrap());
+
+        // the fields are cached as returned by the API, even if empty
+        let fresh = stats::get_players_from_file("testdata/players_converted.json").unwrap();
+        assert!(!is_stale(&serde_json::to_string(&fresh).unwrap()).unwrap());
+
+        let mut players: Vec<Map<String, Value>> =
+            serde_json::from_str(&serde_json::to_string(&fresh).unwrap()).unwrap();
+        players[1].remove("venue");
+        assert!(is_stale(&serde_json::to_string(&players).unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::league::{roster, scoring};
+use crate::stats::{self, output, FantasyPlayer};
+
+use clap::ArgMatches;
+use std::error::Error;
+use std::fs;
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em; color: #24292e; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.25em; margin-top: 2em; border-bottom: 1px solid #eaecef; }
+table { border-collapse: collapse; font-size: 0.9em; }
+th, td { padding: 4px 10px; border: 1px solid #dfe2e5; white-space: nowrap; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; }
+th.asc::after { content: ' \\25B2'; }
+th.desc::after { content: ' \\25BC'; }
+tr:nth-child(even) td { background: #fafbfc; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td.fa { color: #6a737d; }
+.sections { display: flex; flex-wrap: wrap; gap: 2em; }
+";
+
+/// Sorts a table by the clicked column, numbers numerically and the others alphabetically.
+const SCRIPT: &str = "
+document.querySelectorAll('table.sortable th').forEach(function (th, i) {
+  th.addEventListener('click', function () {
+    var table = th.closest('table');
+    var tbody = table.tBodies[0];
+    var desc = !th.classList.contains('desc');
+    table.querySelectorAll('th').forEach(function (h) { h.classList.remove('asc', 'desc'); });
+    th.classList.add(desc ? 'desc' : 'asc');
+    var value = function (tr) {
+      var text = tr.cells[i].textContent.trim();
+      var n = parseFloat(text);
+      return text !== '' && !isNaN(n) && isFinite(text) ? n : text.toLowerCase();
+    };
+    Array.from(tbody.rows)
+      .sort(function (a, b) {
+        var x = value(a), y = value(b);
+        var c = typeof x === typeof y ? (x < y ? -1 : x > y ? 1 : 0) : typeof x === 'number' ? -1 : 1;
+        return desc ? -c : c;
+      })
+      .forEach(function (tr) { tbody.appendChild(tr); });
+  });
+});
+";
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let top_n = matches.value_of("top").unwrap().parse::<usize>()?;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let data = stats::report_data(&config, &s, &r, top_n)?;
+
+    let title = format!(
+        "{} Fantasy Report: {} ({})",
+        league, config.date, config.range
+    );
+    let mut body = vec![format!("<h1>{}</h1>", escape(&title))];
+
+    body.push("<h2>Team Rankings</h2>".to_string());
+    body.push(table(
+        &["#", "Team", "FanPts"],
+        &data
+            .scores
+            .iter()
+            .enumerate()
+            .map(|(i, (team, pts))| vec![(i + 1).to_string(), team.clone(), format!("{:.2}", pts)])
+            .collect::<Vec<_>>(),
+        &[],
+    ));
+
+    body.push(format!(
+        "<h2>Weekly Changes (week of {})</h2>",
+        escape(&config.date)
+    ));
+    let mut headers = vec!["Date"];
+    headers.extend(data.weekly_changes.1.iter().map(|(t, _)| t.as_str()));
+    let (rows, total) = stats::weekly_change_rows(&data.weekly_changes, |p| format!("{:.2}", p));
+    body.push(table(&headers, &rows, &[total]));
+
+    body.push("<h2>Top Performers</h2>".to_string());
+    body.push("<div class=\"sections\"><div>".to_string());
+    body.push(format!("<h3>Top {} Batters</h3>", top_n));
+    body.push(player_table(
+        &data.top_batters,
+        &s.get_header_items_for_batter(),
+    ));
+    body.push("</div><div>".to_string());
+    body.push(format!("<h3>Top {} Pitchers</h3>", top_n));
+    body.push(player_table(
+        &data.top_pitchers,
+        &s.get_header_items_for_pitcher(),
+    ));
+    body.push("</div></div>".to_string());
+
+    body.push("<h2>Players</h2>".to_string());
+    body.push(player_table(&data.players, &s.get_header_items()));
+
+    let html = page(&title, &body.join("\n"));
+    match matches.value_of("output") {
+        Some(path) => {
+            fs::write(path, html)?;
+            println!("report written to {}", path);
+        }
+        None => print!("{}", html),
+    }
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>{}</style>
+</head>
+<body>
+{}
+<script>{}</script>
+</body>
+</html>
+",
+        escape(title),
+        STYLE,
+        body,
+        SCRIPT
+    )
+}
+
+/// Returns a sortable table. Cells of numbers are aligned to the right, and free agents are dimmed.
+/// Footer rows (e.g. totals) are kept at the bottom when sorted.
+fn table<S: AsRef<str>>(headers: &[S], rows: &[Vec<String>], footers: &[Vec<String>]) -> String {
+    let ths: String = headers
+        .iter()
+        .map(|h| format!("<th>{}</th>", escape(h.as_ref())))
+        .collect();
+    let trs = |rows: &[Vec<String>]| {
+        rows.iter()
+            .map(|r| {
+                let tds: String = r
+                    .iter()
+                    .map(|c| {
+                        if c.parse::<f32>().is_ok() {
+                            format!("<td class=\"num\">{}</td>", c)
+                        } else if c == stats::FA {
+                            format!("<td class=\"fa\">{}</td>", escape(c))
+                        } else {
+                            format!("<td>{}</td>", escape(c))
+                        }
+                    })
+                    .collect();
+                format!("<tr>{}</tr>", tds)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let tfoot = if footers.is_empty() {
+        String::new()
+    } else {
+        format!("\n<tfoot>\n{}\n</tfoot>", trs(footers))
+    };
+    format!(
+        "<table class=\"sortable\">\n<thead><tr>{}</tr></thead>\n<tbody>\n{}\n</tbody>{}\n</table>",
+        ths,
+        trs(rows),
+        tfoot
+    )
+}
+
+/// Returns the table of the players with the same columns as `output::print_fantasy_players`.
+fn player_table(players: &[FantasyPlayer], headers: &[String]) -> String {
+    let rows: Vec<Vec<String>> = players
+        .iter()
+        .map(|p| output::get_stat_cells(p, headers))
+        .collect();
+    table(headers, &rows, &[])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn table_should_escape_cells_and_align_numbers() {
+        let rows = vec![
+            vec![
+                "Travis d'Arnaud".to_string(),
+                "<FA>".to_string(),
+                "12.50".to_string(),
+            ],
+            vec!["A & B".to_string(), "LA Bulls".to_string(), "".to_string()],
+        ];
+        let html = table(&["Player", "Team", "FanPts"], &rows, &[]);
+        assert!(html.contains("<th>Player</th><th>Team</th><th>FanPts</th>"));
+        assert!(html.contains(
+            "<tr><td>Travis d&#39;Arnaud</td><td class=\"fa\">&lt;FA&gt;</td><td class=\"num\">12.50</td></tr>"
+        ));
+        assert!(html.contains("<tr><td>A &amp; B</td><td>LA Bulls</td><td></td></tr>"));
+    }
+    #[test]
+    fn table_should_put_footers_in_tfoot() {
+        let rows = vec![vec!["2019-08-19".to_string(), "12.50".to_string()]];
+        let footers = vec![vec!["Total".to_string(), "12.50".to_string()]];
+        let html = table(&["Date", "LA Bulls"], &rows, &footers);
+        assert!(html.contains("<tbody>\n<tr><td>2019-08-19</td>"));
+        assert!(html.contains(
+            "<tfoot>\n<tr><td>Total</td><td class=\"num\">12.50</td></tr>\n</tfoot>\n</table>"
+        ));
+        assert!(!table(&["Date"], &rows, &[]).contains("<tfoot>"));
+    }
+}
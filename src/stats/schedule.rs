@@ -1,5 +1,6 @@
-use crate::stats::Config;
+use crate::stats::{sportradar, Config};
 use crate::utils;
+use log::warn;
 use serde::Deserialize;
 use std::error::Error;
 
@@ -9,15 +10,77 @@ struct Schedule {
 }
 
 #[derive(Deserialize, Debug)]
-struct Game {
-    id: String,
+pub struct Game {
+    pub id: String,
+    pub home: Team,
+    pub away: Team,
+}
+impl Game {
+    /// Returns (MLB team, probable pitcher name, opponent MLB team) for each team
+    /// with a probable pitcher.
+    pub fn probable_starters(&self) -> Vec<(String, String, String)> {
+        [(&self.home, &self.away), (&self.away, &self.home)]
+            .iter()
+            .filter_map(|(t, o)| {
+                t.probable_pitcher
+                    .as_ref()
+                    .map(|pp| (t.abbr.clone(), pp.name(), o.abbr.clone()))
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Team {
+    pub abbr: String,
+    #[serde(default)]
+    pub probable_pitcher: Option<ProbablePitcher>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct ProbablePitcher {
+    pub preferred_name: String,
+    pub last_name: String,
+}
+impl ProbablePitcher {
+    pub fn name(&self) -> String {
+        format!("{} {}", self.preferred_name, self.last_name)
+    }
 }
 
 pub fn get_game_ids(config: &Config, date: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(get_games(config, date)?.into_iter().map(|g| g.id).collect())
+}
+
+pub fn get_games(config: &Config, date: &str) -> Result<Vec<Game>, Box<dyn Error>> {
     let url = get_schedule_url(config, date);
 
     let json = utils::get_json_res(&url)?;
-    Ok(get_game_ids_from_string(json)?)
+    get_games_from_string(json)
+}
+
+/// Same as `get_games`, but probable pitchers missing in the schedule are fetched
+/// from the game summaries.
+pub fn get_games_with_probables(config: &Config, date: &str) -> Result<Vec<Game>, Box<dyn Error>> {
+    let mut games = get_games(config, date)?;
+
+    for g in games.iter_mut() {
+        if g.home.probable_pitcher.is_some() && g.away.probable_pitcher.is_some() {
+            continue;
+        }
+        match sportradar::get_probable_pitchers(config, &g.id) {
+            Ok(pps) => pps.into_iter().for_each(|(abbr, pp)| {
+                if g.home.abbr == abbr {
+                    g.home.probable_pitcher = Some(pp);
+                } else if g.away.abbr == abbr {
+                    g.away.probable_pitcher = Some(pp);
+                }
+            }),
+            Err(e) => warn!("Fetching probable pitchers of game {} failed: {}", g.id, e),
+        }
+    }
+
+    Ok(games)
 }
 
 fn get_schedule_url(config: &Config, date: &str) -> String {
@@ -28,9 +91,9 @@ fn get_schedule_url(config: &Config, date: &str) -> String {
     )
 }
 
-fn get_game_ids_from_string(json: String) -> Result<Vec<String>, Box<dyn Error>> {
+fn get_games_from_string(json: String) -> Result<Vec<Game>, Box<dyn Error>> {
     let schedule: Schedule = serde_json::from_str(&json)?;
-    Ok(schedule.games.into_iter().map(|g| g.id).collect())
+    Ok(schedule.games)
 }
 
 #[cfg(test)]
@@ -49,6 +112,7 @@ mod test {
             0,
             false,
             "",
+            false,
         );
         assert_eq!(
             "https://api.sportradar.us/mlb-t6/games/2019/04/01/schedule.json?api_key=key",
@@ -57,11 +121,44 @@ mod test {
     }
 
     #[test]
-    fn get_game_ids_from_string_should_return_schedule() {
+    fn get_games_from_string_should_return_schedule() {
         use std::fs;
         let json = fs::read_to_string("testdata/schedule.json").unwrap();
-        let ids = get_game_ids_from_string(json).unwrap();
-        assert_eq!(10, ids.len());
-        assert_eq!("07d2922e-3f38-4dbe-a9ea-c96644b7dc10", ids.first().unwrap())
+        let games = get_games_from_string(json).unwrap();
+        assert_eq!(10, games.len());
+
+        let game = games.first().unwrap();
+        assert_eq!("07d2922e-3f38-4dbe-a9ea-c96644b7dc10", game.id);
+        assert_eq!("LAA", game.home.abbr);
+        assert_eq!("MIL", game.away.abbr);
+    }
+
+    #[test]
+    fn probable_starters_should_return_pitchers_with_opponents() {
+        let pitcher = |name: &str| {
+            Some(ProbablePitcher {
+                preferred_name: name.to_string(),
+                last_name: "Smith".to_string(),
+            })
+        };
+        let game = Game {
+            id: "1".to_string(),
+            home: Team {
+                abbr: "NYY".to_string(),
+                probable_pitcher: pitcher("John"),
+            },
+            away: Team {
+                abbr: "BOS".to_string(),
+                probable_pitcher: None,
+            },
+        };
+        assert_eq!(
+            vec![(
+                "NYY".to_string(),
+                "John Smith".to_string(),
+                "BOS".to_string()
+            )],
+            game.probable_starters()
+        );
     }
 }
@@ -0,0 +1,228 @@
+use crate::league::roster;
+use crate::stats;
+use crate::stats::format::{self, Column, Formatter};
+use crate::utils;
+
+use clap::ArgMatches;
+use log::warn;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+
+/// A player found in the stats cache.
+#[derive(Debug, Default, Clone)]
+struct Found {
+    name: String,
+    positions: BTreeSet<String>,
+    mlb_teams: BTreeSet<String>,
+    dates: BTreeSet<String>,
+    distance: usize,
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let query = matches.value_of("query").unwrap();
+    let limit = matches.value_of("limit").unwrap().parse::<usize>()?;
+    let f = format::formatter(matches.value_of("format").unwrap())?;
+    let league = matches.value_of("league").unwrap().to_string();
+    let r = roster::load(&league)?;
+
+    let mut players: HashMap<String, Found> = HashMap::new();
+    for (date, filepath) in stats::cached_stats_files() {
+        let cached = match stats::get_players_from_file(&filepath) {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!("Skipping unreadable stats file {}: {}", filepath, e);
+                continue;
+            }
+        };
+        for p in cached.iter() {
+            let distance = match distance(query, &p.name) {
+                Some(d) => d,
+                None => continue,
+            };
+            let found = players.entry(p.name.to_string()).or_insert_with(|| Found {
+                name: p.name.to_string(),
+                distance,
+                ..Default::default()
+            });
+            found.positions.insert(p.primary_position.to_string());
+            if !p.mlb_team.is_empty() {
+                found.mlb_teams.insert(p.mlb_team.to_string());
+            }
+            found.dates.insert(date.clone());
+        }
+    }
+
+    let mut found: Vec<Found> = players.values().cloned().collect();
+    found.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then(b.dates.len().cmp(&a.dates.len()))
+            .then(a.name.cmp(&b.name))
+    });
+    found.truncate(limit);
+
+    print_found(&found, &r, f.as_ref());
+
+    Ok(())
+}
+
+/// Folds case and accents, e.g. "José Ramírez" to "jose ramirez".
+fn normalize(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            '.' | '\'' | '-' => ' ',
+            _ => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns how far the name is from the query, or None if it does not match.
+/// 0 is an exact match, 1 is a substring match, and larger values are 1 + the edit distance
+/// between the query and the closest part of the name, allowing 1 typo per 4 characters.
+fn distance(query: &str, name: &str) -> Option<usize> {
+    let (query, name) = (normalize(query), normalize(name));
+    if query.is_empty() {
+        return None;
+    }
+    if query == name {
+        return Some(0);
+    }
+    if name.contains(&query) || name.replace(' ', "").contains(&query.replace(' ', "")) {
+        return Some(1);
+    }
+
+    let words: Vec<&str> = name.split(' ').collect();
+    let n = query.split(' ').count();
+    let closest = (0..words.len().saturating_sub(n - 1).max(1))
+        .map(|i| {
+            let part = words[i..(i + n).min(words.len())].join(" ");
+            levenshtein(&query, &part)
+        })
+        .min()
+        .unwrap_or(usize::MAX);
+
+    let max_typos = (query.chars().count() / 4).max(1);
+    if closest <= max_typos {
+        Some(1 + closest)
+    } else {
+        None
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr.push((prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1));
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Compresses sorted dates to ranges of consecutive days, e.g. "07-01~07-03, 07-05".
+fn date_ranges(dates: &BTreeSet<String>) -> String {
+    let mut ranges: Vec<(String, String)> = vec![];
+    for d in dates.iter() {
+        match ranges.last_mut() {
+            Some((_, end)) if utils::add_days(end, 1) == *d => *end = d.clone(),
+            _ => ranges.push((d.clone(), d.clone())),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                start[5..].to_string()
+            } else {
+                format!("{}~{}", &start[5..], &end[5..])
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_found(found: &[Found], r: &roster::Roster, f: &dyn Formatter) {
+    if found.is_empty() {
+        println!("No player found in the stats cache.");
+        return;
+    }
+
+    let columns = [
+        Column::new("Player", 24),
+        Column::new("Pos", 10),
+        Column::new("MLB", 10),
+        Column::new("Roster", 16),
+        Column::new("Days", 6).right(),
+        Column::new("Dates", 6),
+    ];
+    let rows: Vec<Vec<String>> = found
+        .iter()
+        .map(|found| {
+            let rostered = r
+                .players
+                .iter()
+                .find(|p| p.name.to_lowercase() == found.name.to_lowercase())
+                .map(|p| p.team.to_string())
+                .unwrap_or_default();
+            vec![
+                found.name.clone(),
+                found
+                    .positions
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                found
+                    .mlb_teams
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                rostered,
+                found.dates.len().to_string(),
+                date_ranges(&found.dates),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn distance_should_match_names_fuzzily() {
+        assert_eq!(Some(0), distance("jose ramirez", "José Ramírez"));
+        assert_eq!(Some(1), distance("ramirez", "José Ramírez"));
+        assert_eq!(Some(1), distance("jd martinez", "J.D. Martinez"));
+        assert_eq!(Some(2), distance("belinger", "Cody Bellinger"));
+        assert_eq!(Some(3), distance("cody belingr", "Cody Bellinger"));
+        assert_eq!(None, distance("trout", "Cody Bellinger"));
+        assert_eq!(None, distance("", "Cody Bellinger"));
+    }
+
+    #[test]
+    fn date_ranges_should_compress_consecutive_dates() {
+        let dates: BTreeSet<String> = vec!["2019-06-30", "2019-07-01", "2019-07-02", "2019-07-05"]
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!("06-30~07-02, 07-05", date_ranges(&dates));
+    }
+}
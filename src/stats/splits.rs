@@ -0,0 +1,176 @@
+use crate::league::{roster, scoring};
+use crate::stats::{self, format, gamelog, output, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct NoStatsFound(String);
+impl fmt::Display for NoStatsFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no stats found for player {}", self.0)
+    }
+}
+impl Error for NoStatsFound {}
+
+/// (split, games, total stats) of a player.
+type Split<'a> = (String, usize, FantasyPlayer<'a>);
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let names: Vec<&str> = matches.values_of("names").unwrap().collect();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let mut dates = utils::date_strs(&config.date, range);
+    dates.sort();
+
+    let players: Vec<_> = dates
+        .iter()
+        .map(|d| (d.clone(), stats::players_for_date(d.clone(), &config)))
+        .collect();
+
+    for name in names.iter() {
+        let (batting, pitching) = gamelog::game_logs(&players, name, &s, &r);
+        if batting.is_empty() && pitching.is_empty() {
+            return Err(Box::new(NoStatsFound(name.to_string())));
+        }
+
+        for (title, games) in [("Batting", &batting), ("Pitching", &pitching)].iter() {
+            if games.is_empty() {
+                continue;
+            }
+            let fp = &games[0].1;
+            println!(
+                "{}",
+                f.heading(
+                    1,
+                    &format!(
+                        "{} Splits: {} ({}, {} ~ {})",
+                        title,
+                        fp.player.name,
+                        fp.team,
+                        dates.first().unwrap(),
+                        dates.last().unwrap()
+                    )
+                )
+            );
+            output::print_splits(&splits(games), &s, f.as_ref());
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Sums the games by home/away, day/night and opponent, in that order.
+/// Games cached without the split info are left out of the split.
+fn splits<'a>(games: &gamelog::GameLog<'a>) -> Vec<Split<'a>> {
+    let mut home_away: Vec<Split> = vec![];
+    let mut day_night: Vec<Split> = vec![];
+    let mut opponents: Vec<Split> = vec![];
+
+    let add = |splits: &mut Vec<Split<'a>>, split: String, fp: &FantasyPlayer<'a>| match splits
+        .iter_mut()
+        .find(|(s, _, _)| *s == split)
+    {
+        Some((_, games, total)) => {
+            *games += 1;
+            total.add_stats(fp.clone());
+        }
+        None => splits.push((split, 1, fp.clone())),
+    };
+
+    for (_, fp) in games.iter() {
+        match fp.player.home {
+            Some(true) => add(&mut home_away, "Home".to_string(), fp),
+            Some(false) => add(&mut home_away, "Away".to_string(), fp),
+            None => {}
+        }
+        match fp.player.day_night.as_ref() {
+            "D" => add(&mut day_night, "Day".to_string(), fp),
+            "N" => add(&mut day_night, "Night".to_string(), fp),
+            _ => {}
+        }
+        if !fp.player.opponent.is_empty() {
+            add(&mut opponents, format!("vs {}", fp.player.opponent), fp);
+        }
+    }
+
+    home_away.sort_by(|a, b| b.0.cmp(&a.0));
+    day_night.sort_by(|a, b| a.0.cmp(&b.0));
+    opponents.sort_by(|a, b| a.0.cmp(&b.0));
+    home_away
+        .into_iter()
+        .chain(day_night)
+        .chain(opponents)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+    use crate::league::scoring::sample_scoring_rule;
+    use crate::stats::{BatterStats, Player};
+    use std::borrow::Cow;
+
+    #[test]
+    fn splits_should_sum_games_by_home_away_day_night_and_opponent() {
+        let (s, r) = (sample_scoring_rule(), sample_roster());
+        let game = |home: Option<bool>, day_night: &'static str, opponent: &'static str| {
+            let mut p = Player::new_batter(
+                "Cody Bellinger",
+                "OF",
+                "RF",
+                BatterStats {
+                    at_bats: 4,
+                    hits: 1,
+                    singles: 1,
+                    ..Default::default()
+                },
+            );
+            p.home = home;
+            p.day_night = Cow::Borrowed(day_night);
+            p.opponent = Cow::Borrowed(opponent);
+            (
+                "2019-07-01".to_string(),
+                stats::to_fantasy_player(&p, &s, &r),
+            )
+        };
+        let games = vec![
+            game(Some(true), "N", "SF"),
+            game(Some(false), "D", "SF"),
+            game(Some(true), "N", "ARI"),
+            game(None, "", ""),
+        ];
+
+        let splits = splits(&games);
+        assert_eq!(
+            vec![
+                ("Home", 2),
+                ("Away", 1),
+                ("Day", 1),
+                ("Night", 2),
+                ("vs ARI", 1),
+                ("vs SF", 2)
+            ],
+            splits
+                .iter()
+                .map(|(split, games, _)| (split.as_str(), *games))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(8, splits[0].2.player.batter_stats.as_ref().unwrap().at_bats);
+    }
+}
@@ -16,18 +16,40 @@ struct Summary {
 #[derive(Deserialize, Debug)]
 struct Game {
     id: String,
+    #[serde(default)]
+    day_night: String,
+    #[serde(default)]
+    venue: Option<Venue>,
     home: Team,
     away: Team,
 }
 
+#[derive(Deserialize, Debug)]
+struct Venue {
+    name: String,
+}
+
 #[derive(Deserialize, Debug)]
 struct Team {
     abbr: String,
+    #[serde(default)]
+    probable_pitcher: Option<schedule::ProbablePitcher>,
+    #[serde(default)]
     players: Vec<Player>,
 }
 
 #[derive(Deserialize, Debug, Default)]
 pub struct Player {
+    #[serde(default)]
+    pub mlb_team: String,
+    #[serde(default)]
+    pub opponent: String,
+    #[serde(default)]
+    pub home: Option<bool>,
+    #[serde(default)]
+    pub day_night: String,
+    #[serde(default)]
+    pub venue: String,
     pub preferred_name: String,
     pub last_name: String,
     pub status: String,
@@ -194,12 +216,61 @@ fn get_players_from_string(json: String) -> Result<Vec<Player>, Box<dyn Error>>
     let mut summary: Summary = serde_json::from_str(&json)?;
     let mut players: Vec<Player> = Vec::new();
 
-    players.append(&mut summary.game.home.players);
-    players.append(&mut summary.game.away.players);
+    let abbrs = (
+        summary.game.home.abbr.to_owned(),
+        summary.game.away.abbr.to_owned(),
+    );
+    let day_night = summary.game.day_night.to_owned();
+    let venue = summary
+        .game
+        .venue
+        .as_ref()
+        .map(|v| v.name.to_owned())
+        .unwrap_or_default();
+    for (team, abbr, opponent, home) in [
+        (&mut summary.game.home, &abbrs.0, &abbrs.1, true),
+        (&mut summary.game.away, &abbrs.1, &abbrs.0, false),
+    ] {
+        team.players.iter_mut().for_each(|p| {
+            p.mlb_team = abbr.to_owned();
+            p.opponent = opponent.to_owned();
+            p.home = Some(home);
+            p.day_night = day_night.to_owned();
+            p.venue = venue.to_owned();
+        });
+        players.append(&mut team.players);
+    }
 
     Ok(players)
 }
 
+/// Returns (MLB team, pitcher) pairs of the probable starters for given game.
+pub fn get_probable_pitchers(
+    config: &Config,
+    game_id: &str,
+) -> Result<Vec<(String, schedule::ProbablePitcher)>, Box<dyn Error>> {
+    thread::sleep(Duration::from_millis(1050));
+    let url = get_game_summary_url(config, game_id);
+    info!("game summary api url: {}", url);
+
+    let json = utils::get_json_res(&url)?;
+    get_probable_pitchers_from_string(json)
+}
+
+fn get_probable_pitchers_from_string(
+    json: String,
+) -> Result<Vec<(String, schedule::ProbablePitcher)>, Box<dyn Error>> {
+    let summary: Summary = serde_json::from_str(&json)?;
+
+    Ok(vec![summary.game.home, summary.game.away]
+        .into_iter()
+        .filter_map(|t| {
+            let abbr = t.abbr;
+            t.probable_pitcher.map(|pp| (abbr, pp))
+        })
+        .collect())
+}
+
 fn get_game_summary_url(config: &Config, game_id: &str) -> String {
     format!(
         "https://api.sportradar.us/mlb-t6/games/{}/summary.json?api_key={}",
@@ -227,5 +298,33 @@ mod test {
         // println!("pitcher: {:#?}", pitcher);
         assert_eq!(true, pitcher.statistics.pitching.is_some());
         assert_eq!(true, pitcher.statistics.hitting.is_none());
+
+        assert_eq!("LAA", hitter.mlb_team);
+        assert_eq!("MIL", hitter.opponent);
+        assert_eq!(Some(true), hitter.home);
+        assert_eq!("N", hitter.day_night);
+        assert_eq!("Angel Stadium of Anaheim", hitter.venue);
+        let away_pitcher = players.iter().find(|p| p.last_name == "Chacín").unwrap();
+        assert_eq!("MIL", away_pitcher.mlb_team);
+        assert_eq!("LAA", away_pitcher.opponent);
+        assert_eq!(Some(false), away_pitcher.home);
+    }
+
+    #[test]
+    fn get_probable_pitchers_from_string_should_return_probable_pitchers() {
+        use std::fs;
+        let json = fs::read_to_string("testdata/summary.json").unwrap();
+        let probables: Vec<_> = get_probable_pitchers_from_string(json)
+            .unwrap()
+            .into_iter()
+            .map(|(abbr, pp)| (abbr, pp.name()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("LAA".to_string(), "Trevor Cahill".to_string()),
+                ("MIL".to_string(), "Jhoulys Chacín".to_string()),
+            ],
+            probables
+        );
     }
 }
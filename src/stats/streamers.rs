@@ -0,0 +1,438 @@
+use crate::league::{roster, scoring};
+use crate::stats::allowed::{self, Allowed};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, schedule, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use log::info;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+/// A probable start with the recent form of the pitcher and the opponent.
+#[derive(Debug, Default, Clone)]
+struct Start {
+    date: String,
+    pitcher: String,
+    mlb_team: String,
+    opponent: String,
+    team: String,
+    games: usize,
+    per_game: f32,
+    opponent_allowed: Option<f32>,
+    score: f32,
+}
+
+/// Recent fantasy points of pitchers, and fantasy points per game allowed to pitchers by MLB teams.
+#[derive(Debug, Default)]
+struct RecentForm {
+    pitchers: HashMap<String, (usize, f32)>,
+    allowed: HashMap<String, f32>,
+    average_allowed: Option<f32>,
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let days = matches.value_of("days").unwrap().parse::<i64>()?;
+    let free_agents_only = matches.occurrences_of("free_agents") > 0;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let form_range = if config.range == "1d" {
+        "1m"
+    } else {
+        config.range.as_ref()
+    };
+    let per_date = allowed::fantasy_players_per_date(
+        &utils::date_strs(&config.date, form_range),
+        &config,
+        &s,
+        &r,
+    );
+    let form = recent_form(&per_date);
+
+    let upcoming: Vec<String> = (1..=days)
+        .map(|i| utils::add_days(&config.date, i))
+        .collect();
+    let first = upcoming
+        .first()
+        .cloned()
+        .unwrap_or_else(|| config.date.to_string());
+    // the fantasy week of the next date, with the dates up to --date already played
+    let next = utils::add_days(&config.date, 1);
+    let week: Vec<String> = utils::weekly_date_strs(&next)
+        .into_iter()
+        .chain(utils::remaining_week_date_strs(&next))
+        .collect();
+    let played: Vec<String> = week
+        .iter()
+        .filter(|d| d.as_str() <= config.date.as_ref())
+        .cloned()
+        .collect();
+
+    let mut starts = made_starts(
+        &allowed::fantasy_players_per_date(&played, &config, &s, &r),
+        &form,
+        &r,
+    );
+    let dates: BTreeSet<String> = upcoming
+        .iter()
+        .chain(week.iter().filter(|d| d.as_str() > config.date.as_ref()))
+        .cloned()
+        .collect();
+    for d in dates.iter() {
+        thread::sleep(Duration::from_millis(1050));
+        let games = schedule::get_games_with_probables(&config, d)?;
+        info!("{} games scheduled on {}", games.len(), d);
+        games.iter().for_each(|g| {
+            g.probable_starters()
+                .into_iter()
+                .for_each(|(mlb_team, pitcher, opponent)| {
+                    starts.push(create_start(d, pitcher, mlb_team, opponent, &form, &r))
+                })
+        });
+    }
+
+    let two_starts: Vec<_> = two_start_pitchers(&starts, &week)
+        .into_iter()
+        .filter(|(_, sts)| sts.iter().any(|st| st.date.as_str() > config.date.as_ref()))
+        .collect();
+    let mut upcoming_starts: Vec<Start> = starts
+        .into_iter()
+        .filter(|st| upcoming.contains(&st.date))
+        .filter(|st| !free_agents_only || st.team == stats::FA)
+        .collect();
+    upcoming_starts.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then(b.score.partial_cmp(&a.score).unwrap())
+    });
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Probable Starters ({} ~ {}, recent form: {})",
+                first,
+                upcoming.last().unwrap_or(&first),
+                form_range
+            )
+        )
+    );
+    print_starts(&upcoming_starts, &two_starts, f.as_ref());
+    println!();
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Two-start Pitchers of the Week ({} ~ {})",
+                week.first().unwrap_or(&first),
+                week.last().unwrap_or(&first)
+            )
+        )
+    );
+    print_two_starts(
+        &two_starts
+            .into_iter()
+            .filter(|(_, sts)| !free_agents_only || sts[0].team == stats::FA)
+            .collect::<Vec<_>>(),
+        f.as_ref(),
+    );
+
+    Ok(())
+}
+
+fn recent_form(per_date: &[(String, Vec<FantasyPlayer>)]) -> RecentForm {
+    let mut pitchers = HashMap::new();
+    per_date
+        .iter()
+        .flat_map(|(_, fps)| fps.iter())
+        .filter(|fp| fp.player.pitcher_stats.is_some())
+        .for_each(|fp| {
+            let p = pitchers
+                .entry(fp.player.name.to_lowercase())
+                .or_insert((0, 0.0));
+            p.0 += 1;
+            p.1 += fp.fantasy_points;
+        });
+
+    let allowed: Vec<Allowed> = allowed::points_allowed(per_date);
+    RecentForm {
+        pitchers,
+        average_allowed: if allowed.is_empty() {
+            None
+        } else {
+            Some(allowed::averages(&allowed).1)
+        },
+        allowed: allowed
+            .iter()
+            .map(|a| (a.team.clone(), a.to_pitchers_per_game()))
+            .collect(),
+    }
+}
+
+fn create_start(
+    date: &str,
+    pitcher: String,
+    mlb_team: String,
+    opponent: String,
+    form: &RecentForm,
+    r: &roster::Roster,
+) -> Start {
+    let (games, points) = form
+        .pitchers
+        .get(&pitcher.to_lowercase())
+        .cloned()
+        .unwrap_or((0, 0.0));
+    let per_game = if games > 0 {
+        points / games as f32
+    } else {
+        0.0
+    };
+    let team = r
+        .players
+        .iter()
+        .find(|p| p.name.to_lowercase() == pitcher.to_lowercase() && p.is_pitcher())
+        .map(|p| p.team.to_string())
+        .unwrap_or_else(|| stats::FA.to_string());
+    let opponent_allowed = form.allowed.get(&opponent).cloned();
+
+    Start {
+        date: date.to_string(),
+        score: score(per_game, opponent_allowed, form.average_allowed),
+        pitcher,
+        mlb_team,
+        opponent,
+        team,
+        games,
+        per_game,
+        opponent_allowed,
+    }
+}
+
+/// Returns the starts made on given dates. Box scores don't tell starters from relievers,
+/// so the starting pitchers (SP) who pitched are counted as starters.
+fn made_starts(
+    per_date: &[(String, Vec<FantasyPlayer>)],
+    form: &RecentForm,
+    r: &roster::Roster,
+) -> Vec<Start> {
+    per_date
+        .iter()
+        .flat_map(|(date, fps)| fps.iter().map(move |fp| (date, fp)))
+        .filter(|(_, fp)| fp.player.pitcher_stats.is_some() && fp.player.primary_position == "SP")
+        .map(|(date, fp)| {
+            create_start(
+                date,
+                fp.player.name.to_string(),
+                fp.player.mlb_team.to_string(),
+                fp.player.opponent.to_string(),
+                form,
+                r,
+            )
+        })
+        .collect()
+}
+
+/// Points per game of the pitcher scaled by the points per game the opponent allows to pitchers
+/// compared to the league average, e.g. 1.25x against an offense allowing 125% of the average.
+fn score(per_game: f32, opponent_allowed: Option<f32>, average_allowed: Option<f32>) -> f32 {
+    match (opponent_allowed, average_allowed) {
+        (Some(a), Some(avg)) if avg > 0.0 => per_game * a / avg,
+        _ => per_game,
+    }
+}
+
+/// Returns the pitchers with two or more probable starts in given week dates,
+/// sorted by the sum of their scores.
+fn two_start_pitchers(starts: &[Start], week: &[String]) -> Vec<(String, Vec<Start>)> {
+    let mut pitchers: Vec<(String, Vec<Start>)> = vec![];
+    starts
+        .iter()
+        .filter(|st| week.contains(&st.date))
+        .for_each(
+            |st| match pitchers.iter_mut().find(|(p, _)| *p == st.pitcher) {
+                Some((_, sts)) => sts.push(st.clone()),
+                None => pitchers.push((st.pitcher.clone(), vec![st.clone()])),
+            },
+        );
+
+    let total = |sts: &[Start]| sts.iter().map(|st| st.score).sum::<f32>();
+    let mut pitchers: Vec<_> = pitchers
+        .into_iter()
+        .filter(|(_, sts)| sts.len() >= 2)
+        .collect();
+    pitchers.sort_by(|a, b| total(&b.1).partial_cmp(&total(&a.1)).unwrap());
+    pitchers
+}
+
+fn print_starts(starts: &[Start], two_starts: &[(String, Vec<Start>)], f: &dyn Formatter) {
+    let columns = [
+        Column::new("Date", 12),
+        Column::new("Pitcher", 20),
+        Column::new("MLB", 5),
+        Column::new("Opp", 5),
+        Column::new("Team", 20),
+        Column::new("G", 4).right(),
+        Column::new("Pts/G", 8).right(),
+        Column::new("OppAlw", 8).right(),
+        Column::new("Score", 8).right(),
+        Column::new("2S", 4).right(),
+    ];
+    let rows: Vec<Vec<String>> = starts
+        .iter()
+        .map(|st| {
+            let two_start = two_starts.iter().any(|(p, _)| *p == st.pitcher);
+            vec![
+                st.date.clone(),
+                st.pitcher.clone(),
+                st.mlb_team.clone(),
+                st.opponent.clone(),
+                st.team.clone(),
+                st.games.to_string(),
+                format!("{:.2}", st.per_game),
+                st.opponent_allowed
+                    .map(|o| format!("{:.2}", o))
+                    .unwrap_or_else(|| "-".to_string()),
+                format!("{:.2}", st.score),
+                if two_start { "*" } else { "" }.to_string(),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_two_starts(two_starts: &[(String, Vec<Start>)], f: &dyn Formatter) {
+    let columns = [
+        Column::new("Pitcher", 20),
+        Column::new("MLB", 5),
+        Column::new("Team", 20),
+        Column::new("Starts", 30),
+        Column::new("Pts/G", 8).right(),
+        Column::new("Score", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = two_starts
+        .iter()
+        .map(|(pitcher, sts)| {
+            let st = &sts[0];
+            let dates = sts
+                .iter()
+                .map(|st| format!("{} vs {}", &st.date[5..], st.opponent))
+                .collect::<Vec<_>>();
+            vec![
+                pitcher.clone(),
+                st.mlb_team.clone(),
+                st.team.clone(),
+                dates.join(", "),
+                format!("{:.2}", st.per_game),
+                format!("{:.2}", sts.iter().map(|st| st.score).sum::<f32>()),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stats::{BatterStats, PitcherStats, Player};
+    use crate::utils::assert_eq_f32;
+    use std::borrow::Cow;
+    use std::rc::Rc;
+
+    #[test]
+    fn score_should_scale_points_by_opponent_offense() {
+        assert_eq_f32(12.5, score(10.0, Some(12.5), Some(10.0)));
+        assert_eq_f32(8.0, score(10.0, Some(8.0), Some(10.0)));
+        assert_eq_f32(10.0, score(10.0, None, Some(10.0)));
+    }
+
+    #[test]
+    fn two_start_pitchers_should_return_pitchers_starting_twice_in_week() {
+        let start = |date: &str, pitcher: &str, score: f32| Start {
+            date: date.to_string(),
+            pitcher: pitcher.to_string(),
+            score,
+            ..Default::default()
+        };
+        let starts = vec![
+            start("2019-07-21", "Blake Snell", 10.0),
+            start("2019-07-22", "Max Scherzer", 20.0),
+            start("2019-07-23", "Blake Snell", 12.0),
+            start("2019-07-24", "Jacob deGrom", 15.0),
+            start("2019-07-27", "Max Scherzer", 20.0),
+            start("2019-07-28", "Jacob deGrom", 14.0),
+        ];
+        let week = utils::weekly_date_strs("2019-07-28");
+
+        let two_starts = two_start_pitchers(&starts, &week);
+        assert_eq!(
+            vec!["Max Scherzer", "Jacob deGrom"],
+            two_starts
+                .iter()
+                .map(|(p, _)| p.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(2, two_starts[0].1.len());
+    }
+
+    #[test]
+    fn made_starts_should_return_starting_pitchers_who_pitched() {
+        let fp = |name: &'static str, primary_position: &'static str, pitched: bool| {
+            let mut player = if pitched {
+                Player::new_pitcher(name, "P", primary_position, PitcherStats::default())
+            } else {
+                Player::new_batter(name, "P", primary_position, BatterStats::default())
+            };
+            player.mlb_team = "TB".into();
+            player.opponent = "NYY".into();
+            FantasyPlayer {
+                team: Rc::new(Cow::Borrowed(stats::FA)),
+                player,
+                fantasy_points: 0.0,
+            }
+        };
+        let per_date = vec![
+            (
+                "2019-07-22".to_string(),
+                vec![
+                    fp("Blake Snell", "SP", true),
+                    fp("Diego Castillo", "RP", true),
+                ],
+            ),
+            (
+                "2019-07-23".to_string(),
+                vec![
+                    fp("Charlie Morton", "SP", true),
+                    fp("Tyler Glasnow", "SP", false),
+                ],
+            ),
+        ];
+
+        let starts = made_starts(
+            &per_date,
+            &RecentForm::default(),
+            &roster::Roster::default(),
+        );
+        assert_eq!(
+            vec![
+                ("2019-07-22", "Blake Snell"),
+                ("2019-07-23", "Charlie Morton")
+            ],
+            starts
+                .iter()
+                .map(|st| (st.date.as_str(), st.pitcher.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("NYY", starts[0].opponent);
+        assert_eq!(stats::FA, starts[0].team);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, Config};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct PlayerNotRostered(String);
+impl fmt::Display for PlayerNotRostered {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "player {} is not on any team of the league roster",
+            self.0
+        )
+    }
+}
+impl Error for PlayerNotRostered {}
+
+#[derive(Debug, Clone)]
+struct InvalidTrade(String);
+impl fmt::Display for InvalidTrade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid trade: {}", self.0)
+    }
+}
+impl Error for InvalidTrade {}
+
+/// Players given up by a fantasy team, keyed by `stats::player_key`.
+#[derive(Debug, Default, Clone)]
+struct Side {
+    team: String,
+    players: Vec<(String, String)>,
+}
+
+/// Fantasy points and games of every rostered player over a range.
+#[derive(Debug, Default, Clone)]
+struct RangePoints {
+    range: String,
+    players: HashMap<String, (f32, usize)>,
+}
+impl RangePoints {
+    fn points(&self, key: &str) -> f32 {
+        self.players.get(key).map(|p| p.0).unwrap_or(0.0)
+    }
+
+    fn per_game(&self, key: &str) -> f32 {
+        match self.players.get(key) {
+            Some(&(pts, games)) if games > 0 => pts / games as f32,
+            _ => 0.0,
+        }
+    }
+
+    fn side_points(&self, side: &Side) -> (f32, f32) {
+        side.players.iter().fold((0.0, 0.0), |acc, (key, _)| {
+            (acc.0 + self.points(key), acc.1 + self.per_game(key))
+        })
+    }
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let ranges: Vec<&str> = matches.values_of("ranges").unwrap().collect();
+    let side_a: Vec<&str> = matches.values_of("side_a").unwrap().collect();
+    let side_b: Vec<&str> = matches.values_of("side_b").unwrap().collect();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let sides = (resolve_side(&side_a, &r)?, resolve_side(&side_b, &r)?);
+    if sides.0.team == sides.1.team {
+        return Err(Box::new(InvalidTrade(format!(
+            "both sides belong to {}",
+            sides.0.team
+        ))));
+    }
+
+    let teams: HashMap<String, String> = r
+        .players
+        .iter()
+        .map(|p| (roster_key(p), p.team.to_string()))
+        .collect();
+    let mut traded = teams.clone();
+    sides.0.players.iter().for_each(|(key, _)| {
+        traded.insert(key.clone(), sides.1.team.clone());
+    });
+    sides.1.players.iter().for_each(|(key, _)| {
+        traded.insert(key.clone(), sides.0.team.clone());
+    });
+
+    let points = range_points(&ranges, &config, &s, &r);
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Trade: {} gives {} / {} gives {}",
+                sides.0.team,
+                side_names(&sides.0),
+                sides.1.team,
+                side_names(&sides.1)
+            )
+        )
+    );
+    print_players(&sides, &points, f.as_ref());
+    println!();
+    print_net_impact(&sides, &points, f.as_ref());
+    points.iter().for_each(|rp| {
+        println!();
+        print_rankings(rp, &teams, &traded, f.as_ref());
+    });
+
+    Ok(())
+}
+
+fn roster_key(p: &roster::Player) -> String {
+    stats::player_key(&p.name, p.is_pitcher())
+}
+
+fn resolve_side(names: &[&str], r: &roster::Roster) -> Result<Side, Box<dyn Error>> {
+    let mut side = Side::default();
+    for name in names.iter() {
+        let found: Vec<_> = r
+            .players
+            .iter()
+            .filter(|p| p.name.to_lowercase() == name.trim().to_lowercase())
+            .collect();
+        if found.is_empty() {
+            return Err(Box::new(PlayerNotRostered(name.to_string())));
+        }
+
+        for p in found.into_iter() {
+            if side.team.is_empty() {
+                side.team = p.team.to_string();
+            } else if side.team != p.team.as_ref().as_ref() {
+                return Err(Box::new(InvalidTrade(format!(
+                    "{} is on {}, not on {}",
+                    p.name, p.team, side.team
+                ))));
+            }
+            side.players.push((roster_key(p), p.name.to_string()));
+        }
+    }
+    Ok(side)
+}
+
+fn side_names(side: &Side) -> String {
+    let names: BTreeSet<&str> = side.players.iter().map(|(_, n)| n.as_str()).collect();
+    names.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+/// Stats of each date are loaded once and shared by all the ranges.
+fn range_points(
+    ranges: &[&str],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> Vec<RangePoints> {
+    let mut per_date: HashMap<String, Vec<(String, f32)>> = HashMap::new();
+
+    ranges
+        .iter()
+        .map(|range| {
+            let mut rp = RangePoints {
+                range: range.to_string(),
+                ..Default::default()
+            };
+            for d in utils::date_strs(&config.date, range).into_iter() {
+                let date_points = per_date.entry(d.clone()).or_insert_with(|| {
+                    let players = stats::players_for_date(d, config);
+                    stats::create_fantasy_players(&players, s, r, false)
+                        .unwrap()
+                        .into_iter()
+                        .map(|fp| (fp.key(), fp.fantasy_points))
+                        .collect()
+                });
+                date_points.iter().for_each(|(key, pts)| {
+                    let p = rp.players.entry(key.clone()).or_insert((0.0, 0));
+                    p.0 += pts;
+                    p.1 += 1;
+                });
+            }
+            rp
+        })
+        .collect()
+}
+
+/// Returns team totals sorted by fantasy points like `print_scores_per_team`.
+fn team_rankings(rp: &RangePoints, teams: &HashMap<String, String>) -> Vec<(String, f32)> {
+    let mut scores: Vec<(String, f32)> = teams
+        .iter()
+        .fold(HashMap::new(), |mut acc, (key, team)| {
+            *acc.entry(team.clone()).or_insert(0.0) += rp.points(key);
+            acc
+        })
+        .into_iter()
+        .collect();
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+    scores
+}
+
+fn print_players(sides: &(Side, Side), points: &[RangePoints], f: &dyn Formatter) {
+    let mut columns = vec![Column::new("Player", 20), Column::new("Team", 20)];
+    points.iter().for_each(|rp| {
+        columns.push(Column::new(&format!("Pts({})", rp.range), 10).right());
+        columns.push(Column::new(&format!("Pts/G({})", rp.range), 10).right());
+    });
+
+    let rows: Vec<Vec<String>> = [&sides.0, &sides.1]
+        .iter()
+        .flat_map(|side| {
+            side.players.iter().map(move |(key, name)| {
+                let mut row = vec![name.clone(), side.team.clone()];
+                points.iter().for_each(|rp| {
+                    row.push(format!("{:.2}", rp.points(key)));
+                    row.push(format!("{:.2}", rp.per_game(key)));
+                });
+                row
+            })
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+/// Net of each team is the points of the players received minus the points of the players
+/// given up. The per game net is the rate going forward.
+fn print_net_impact(sides: &(Side, Side), points: &[RangePoints], f: &dyn Formatter) {
+    println!("{}", f.heading(1, "Net Impact"));
+    let columns = [
+        Column::new("Range", 8),
+        Column::new("Team", 20),
+        Column::new("Net", 9).right().points(),
+        Column::new("Net/G", 9).right().points(),
+        Column::new("Opponent", 20),
+        Column::new("OppNet", 9).right().points(),
+        Column::new("OppNet/G", 9).right().points(),
+    ];
+    let rows: Vec<Vec<String>> = points
+        .iter()
+        .map(|rp| {
+            let (a_pts, a_rate) = rp.side_points(&sides.0);
+            let (b_pts, b_rate) = rp.side_points(&sides.1);
+            vec![
+                rp.range.clone(),
+                sides.0.team.clone(),
+                format!("{:+.2}", b_pts - a_pts),
+                format!("{:+.2}", b_rate - a_rate),
+                sides.1.team.clone(),
+                format!("{:+.2}", a_pts - b_pts),
+                format!("{:+.2}", a_rate - b_rate),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_rankings(
+    rp: &RangePoints,
+    teams: &HashMap<String, String>,
+    traded: &HashMap<String, String>,
+    f: &dyn Formatter,
+) {
+    let before = team_rankings(rp, teams);
+    let after = team_rankings(rp, traded);
+    let rank_of = |team: &str| after.iter().position(|(t, _)| t == team).unwrap() + 1;
+
+    println!("{}", f.heading(1, &format!("Team Rankings ({})", rp.range)));
+    let columns = [
+        Column::new("Team", 20),
+        Column::new("Before", 9).right(),
+        Column::new("After", 9).right(),
+        Column::new("Change", 9).right().points(),
+        Column::new("Rank", 6).right(),
+        Column::new("NewRank", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = before
+        .iter()
+        .enumerate()
+        .map(|(i, (team, pts))| {
+            let new_pts = after
+                .iter()
+                .find(|(t, _)| t == team)
+                .map(|(_, p)| *p)
+                .unwrap();
+            vec![
+                team.clone(),
+                format!("{:.2}", pts),
+                format!("{:.2}", new_pts),
+                format!("{:+.2}", new_pts - pts),
+                (i + 1).to_string(),
+                rank_of(team).to_string(),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::league::roster::sample_roster;
+
+    #[test]
+    fn resolve_side_should_return_players_of_a_team() {
+        let r = sample_roster();
+
+        let side = resolve_side(&["cody bellinger", "Blake Snell"], &r).unwrap();
+        assert_eq!("LA Bulls", side.team);
+        assert_eq!(
+            vec!["cody bellinger:b".to_string(), "blake snell:p".to_string()],
+            side.players
+                .iter()
+                .map(|(k, _)| k.clone())
+                .collect::<Vec<_>>()
+        );
+
+        assert!(resolve_side(&["Cody Bellinger", "Josh Hader"], &r).is_err());
+        assert!(resolve_side(&["Mike Trout"], &r).is_err());
+    }
+
+    #[test]
+    fn team_rankings_should_reflect_traded_players() {
+        let r = sample_roster();
+        let mut rp = RangePoints {
+            range: "2w".to_string(),
+            ..Default::default()
+        };
+        rp.players
+            .insert("cody bellinger:b".to_string(), (30.0, 10));
+        rp.players
+            .insert("christian yelich:b".to_string(), (20.0, 10));
+        rp.players.insert("blake snell:p".to_string(), (5.0, 2));
+
+        let teams: HashMap<String, String> = r
+            .players
+            .iter()
+            .map(|p| (roster_key(p), p.team.to_string()))
+            .collect();
+        let before = team_rankings(&rp, &teams);
+        assert_eq!(("LA Bulls".to_string(), 35.0), before[0]);
+        assert_eq!(("Chicago Pizzas".to_string(), 20.0), before[1]);
+
+        let mut traded = teams.clone();
+        traded.insert("cody bellinger:b".to_string(), "Chicago Pizzas".to_string());
+        traded.insert("christian yelich:b".to_string(), "LA Bulls".to_string());
+        let after = team_rankings(&rp, &traded);
+        assert_eq!(("Chicago Pizzas".to_string(), 30.0), after[0]);
+        assert_eq!(("LA Bulls".to_string(), 25.0), after[1]);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::league::{roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, consistency, Config, FantasyPlayer};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Minimum games in the recent window and in the baseline to be considered a streak.
+const MIN_RECENT_GAMES: usize = 3;
+const MIN_BASELINE_GAMES: usize = 5;
+
+/// Fantasy points per game of a player in the recent window compared to the baseline.
+#[derive(Debug, Default, Clone)]
+struct Trend {
+    name: String,
+    team: String,
+    position: String,
+    recent_games: usize,
+    recent_per_game: f32,
+    baseline_games: usize,
+    baseline_per_game: f32,
+    z: f32,
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let window = matches.value_of("window").unwrap();
+    let min_z = matches.value_of("min_z").unwrap().parse::<f32>()?;
+    let limit = matches.value_of("limit").unwrap().parse::<usize>()?;
+    let free_agents_only = matches.occurrences_of("free_agents") > 0;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let baseline_range = if config.range == "1d" {
+        "all"
+    } else {
+        config.range.as_ref()
+    };
+    let recent_dates = utils::date_strs(&config.date, window);
+    let baseline_dates: Vec<String> = utils::date_strs(&config.date, baseline_range)
+        .into_iter()
+        .filter(|d| !recent_dates.contains(d))
+        .collect();
+
+    let recent = points_per_player(&recent_dates, &config, &s, &r);
+    let baseline = points_per_player(&baseline_dates, &config, &s, &r);
+    let trends: Vec<Trend> = trends(&recent, &baseline)
+        .into_iter()
+        .filter(|t| !free_agents_only || t.team == stats::FA)
+        .collect();
+
+    let mut hot: Vec<&Trend> = trends.iter().filter(|t| t.z >= min_z).collect();
+    hot.sort_by(|a, b| b.z.partial_cmp(&a.z).unwrap());
+    hot.truncate(limit);
+    let mut cold: Vec<&Trend> = trends.iter().filter(|t| t.z <= -min_z).collect();
+    cold.sort_by(|a, b| a.z.partial_cmp(&b.z).unwrap());
+    cold.truncate(limit);
+
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Hot Players ({} vs {}, until {}, z >= {})",
+                window, baseline_range, config.date, min_z
+            )
+        )
+    );
+    print_trends(&hot, f.as_ref());
+    println!();
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Cold Players ({} vs {}, until {}, z <= -{})",
+                window, baseline_range, config.date, min_z
+            )
+        )
+    );
+    print_trends(&cold, f.as_ref());
+
+    Ok(())
+}
+
+/// Daily fantasy points of a player with the name, team and position to show.
+#[derive(Debug, Default, Clone)]
+struct PlayerPoints {
+    name: String,
+    team: String,
+    position: String,
+    points: Vec<f32>,
+}
+
+/// Points of each player keyed by `FantasyPlayer::key`.
+type Points = HashMap<String, PlayerPoints>;
+
+fn points_per_player(
+    dates: &[String],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> Points {
+    let mut points = Points::new();
+    for d in dates.iter() {
+        let players = stats::players_for_date(d.clone(), config);
+        let fplayers = stats::create_fantasy_players(&players, s, r, true).unwrap();
+        add_points(&mut points, &fplayers);
+    }
+    points
+}
+
+fn add_points(points: &mut Points, fplayers: &[FantasyPlayer]) {
+    fplayers.iter().for_each(|fp| {
+        points
+            .entry(fp.key())
+            .or_insert_with(|| PlayerPoints {
+                name: fp.player.name.to_string(),
+                team: fp.team.to_string(),
+                position: fp.player.primary_position.to_string(),
+                points: vec![],
+            })
+            .points
+            .push(fp.fantasy_points);
+    });
+}
+
+/// Compares the recent points per game of each player with the baseline by z-score,
+/// i.e. how many standard errors of the baseline the recent average is away from it.
+fn trends(recent: &Points, baseline: &Points) -> Vec<Trend> {
+    recent
+        .iter()
+        .filter_map(|(key, recent)| {
+            let (recent_pts, baseline_pts) = (&recent.points, &baseline.get(key)?.points);
+            if recent_pts.len() < MIN_RECENT_GAMES || baseline_pts.len() < MIN_BASELINE_GAMES {
+                return None;
+            }
+
+            let r = consistency::Consistency::new(recent_pts, 0.0, 0.0);
+            let b = consistency::Consistency::new(baseline_pts, 0.0, 0.0);
+            if b.std_dev <= 0.0 {
+                return None;
+            }
+            let std_err = b.std_dev / (r.games as f32).sqrt();
+
+            Some(Trend {
+                name: recent.name.clone(),
+                team: recent.team.clone(),
+                position: recent.position.clone(),
+                recent_games: r.games,
+                recent_per_game: r.mean,
+                baseline_games: b.games,
+                baseline_per_game: b.mean,
+                z: (r.mean - b.mean) / std_err,
+            })
+        })
+        .collect()
+}
+
+fn print_trends(trends: &[&Trend], f: &dyn Formatter) {
+    if trends.is_empty() {
+        println!("No player found.");
+        return;
+    }
+
+    let columns = [
+        Column::new("Player", 20).truncated(),
+        Column::new("Team", 20).truncated(),
+        Column::new("Pos", 5),
+        Column::new("G", 4).right(),
+        Column::new("Pts/G", 8).right(),
+        Column::new("BaseG", 6).right(),
+        Column::new("BasePts/G", 10).right(),
+        Column::new("Diff", 8).right().points(),
+        Column::new("Z", 7).right(),
+    ];
+    let rows: Vec<Vec<String>> = trends
+        .iter()
+        .map(|t| {
+            vec![
+                t.name.clone(),
+                t.team.clone(),
+                t.position.clone(),
+                t.recent_games.to_string(),
+                format!("{:.2}", t.recent_per_game),
+                t.baseline_games.to_string(),
+                format!("{:.2}", t.baseline_per_game),
+                format!("{:+.2}", t.recent_per_game - t.baseline_per_game),
+                format!("{:+.2}", t.z),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stats::{BatterStats, Player};
+    use crate::utils::assert_eq_f32;
+    use std::borrow::Cow;
+    use std::rc::Rc;
+
+    #[test]
+    fn trends_should_compare_recent_points_with_baseline() {
+        let points = |entries: Vec<(&str, Vec<f32>)>| -> Points {
+            let mut points = Points::new();
+            entries.into_iter().for_each(|(name, pts)| {
+                let fplayers: Vec<_> = pts
+                    .into_iter()
+                    .map(|fantasy_points| FantasyPlayer {
+                        team: Rc::new(Cow::Borrowed(stats::FA)),
+                        player: Player::new_batter(name, "OF", "CF", BatterStats::default()),
+                        fantasy_points,
+                    })
+                    .collect();
+                add_points(&mut points, &fplayers);
+            });
+            points
+        };
+        let recent = points(vec![
+            ("Mike Trout", vec![12.0, 10.0, 14.0]),
+            ("Cody Bellinger", vec![1.0, 2.0, 0.0]),
+            ("Joey Gallo", vec![10.0, 10.0]),
+        ]);
+        let baseline = points(vec![
+            ("Mike Trout", vec![4.0, 6.0, 8.0, 2.0, 5.0]),
+            ("Cody Bellinger", vec![8.0, 6.0, 10.0, 4.0, 7.0]),
+            ("Joey Gallo", vec![2.0, 4.0, 6.0, 2.0, 3.0]),
+        ]);
+
+        let mut trends = trends(&recent, &baseline);
+        trends.sort_by(|a, b| b.z.partial_cmp(&a.z).unwrap());
+
+        assert_eq!(2, trends.len());
+        assert_eq!("Mike Trout", trends[0].name);
+        assert_eq!(stats::FA, trends[0].team);
+        assert_eq_f32(12.0, trends[0].recent_per_game);
+        assert_eq_f32(5.0, trends[0].baseline_per_game);
+        assert_eq_f32(6.0622, trends[0].z);
+        assert_eq!("Cody Bellinger", trends[1].name);
+        assert!(trends[1].z < -2.0);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::league::{matchup, roster, scoring};
+use crate::stats::format::{self, Column, Formatter};
+use crate::stats::{self, Config};
+use crate::utils;
+
+use clap::ArgMatches;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+struct UnknownTeam(String);
+impl fmt::Display for UnknownTeam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "team {} is not found in the league roster", self.0)
+    }
+}
+impl Error for UnknownTeam {}
+
+#[derive(Debug, Clone)]
+struct NoDatesInRange(String, String);
+impl fmt::Display for NoDatesInRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no game dates in range {} by {}", self.1, self.0)
+    }
+}
+impl Error for NoDatesInRange {}
+
+/// Fantasy points of a player summed over the range.
+#[derive(Debug, Default, Clone)]
+struct Candidate {
+    name: String,
+    team: String,
+    pitcher: bool,
+    primary_position: String,
+    games: usize,
+    points: f32,
+}
+impl Candidate {
+    fn per_game(&self) -> f32 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.points / self.games as f32
+    }
+
+    fn value(&self, per_game: bool) -> f32 {
+        if per_game {
+            self.per_game()
+        } else {
+            self.points
+        }
+    }
+
+    fn is_free_agent(&self) -> bool {
+        self.team == stats::FA
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Swap {
+    add: Candidate,
+    drop: Candidate,
+    gain: f32,
+}
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let f = format::formatter(&config.format)?;
+    let team = matches.value_of("team").unwrap();
+    let limit = matches.value_of("limit").unwrap().parse::<usize>()?;
+    let per_game = matches.occurrences_of("per_game") > 0;
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+    if !matchup::team_names(&r).iter().any(|t| t == team) {
+        return Err(Box::new(UnknownTeam(team.to_string())));
+    }
+
+    let dates = utils::date_strs(&config.date, &config.range);
+    let (first, last) = match (dates.iter().min(), dates.iter().max()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            return Err(Box::new(NoDatesInRange(
+                config.date.to_string(),
+                config.range.to_string(),
+            )))
+        }
+    };
+    let mut candidates = candidates(&dates, &config, &s, &r)?;
+    candidates.sort_by(|a, b| b.value(per_game).partial_cmp(&a.value(per_game)).unwrap());
+
+    let free_agents: Vec<Candidate> = candidates
+        .iter()
+        .filter(|c| c.is_free_agent())
+        .cloned()
+        .collect();
+    let rostered: Vec<Candidate> = candidates.into_iter().filter(|c| c.team == team).collect();
+
+    println!(
+        "{}",
+        f.heading(1, &format!("Top Free Agents ({} ~ {})", first, last))
+    );
+    print_free_agents(&free_agents[..limit.min(free_agents.len())], f.as_ref());
+    println!();
+    println!(
+        "{}",
+        f.heading(
+            1,
+            &format!(
+                "Suggested Swaps for {} (by {})",
+                team,
+                if per_game {
+                    "points per game"
+                } else {
+                    "points"
+                }
+            )
+        )
+    );
+    print_swaps(
+        &suggest_swaps(&free_agents, &rostered, per_game, limit),
+        f.as_ref(),
+    );
+
+    Ok(())
+}
+
+/// Rostered players without any stats in the range are also included with zero games.
+fn candidates(
+    dates: &[String],
+    config: &Config,
+    s: &scoring::ScoringRule,
+    r: &roster::Roster,
+) -> Result<Vec<Candidate>, Box<dyn Error>> {
+    let mut candidates: Vec<Candidate> = r
+        .players
+        .iter()
+        .map(|p| Candidate {
+            name: p.name.to_string(),
+            team: p.team.to_string(),
+            pitcher: p.is_pitcher(),
+            ..Default::default()
+        })
+        .collect();
+    // indices of the candidates keyed by `stats::player_key`
+    let mut indices: HashMap<String, usize> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (stats::player_key(&c.name, c.pitcher), i))
+        .collect();
+
+    for d in dates.iter() {
+        let players = stats::players_for_date(d.clone(), config);
+        let fplayers = stats::create_fantasy_players(&players, s, r, true)?;
+        fplayers
+            .into_iter()
+            .for_each(|fp| match indices.get(&fp.key()) {
+                Some(&i) => {
+                    let c = &mut candidates[i];
+                    c.games += 1;
+                    c.points += fp.fantasy_points;
+                    c.primary_position = fp.player.primary_position.to_string();
+                }
+                None => {
+                    indices.insert(fp.key(), candidates.len());
+                    candidates.push(Candidate {
+                        name: fp.player.name.to_string(),
+                        team: fp.team.to_string(),
+                        pitcher: fp.player.pitcher_stats.is_some(),
+                        primary_position: fp.player.primary_position.to_string(),
+                        games: 1,
+                        points: fp.fantasy_points,
+                    });
+                }
+            });
+    }
+
+    Ok(candidates)
+}
+
+/// Each free agent (best first) replaces the weakest remaining rostered player of the same
+/// position, or of the same role if the team has no player of the position. Rostered players
+/// without games in the range, e.g. injured ones, are never dropped, as their position is unknown.
+fn suggest_swaps(
+    free_agents: &[Candidate],
+    rostered: &[Candidate],
+    per_game: bool,
+    limit: usize,
+) -> Vec<Swap> {
+    let mut droppable: Vec<Candidate> = rostered.iter().filter(|c| c.games > 0).cloned().collect();
+    let mut swaps = vec![];
+
+    for fa in free_agents.iter() {
+        if swaps.len() >= limit {
+            break;
+        }
+
+        let weakest = |same_position: bool| {
+            droppable
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.pitcher == fa.pitcher)
+                .filter(|(_, c)| !same_position || c.primary_position == fa.primary_position)
+                .min_by(|(_, a), (_, b)| a.value(per_game).partial_cmp(&b.value(per_game)).unwrap())
+                .map(|(i, _)| i)
+        };
+        let i = match weakest(true).or_else(|| weakest(false)) {
+            Some(i) => i,
+            None => continue,
+        };
+
+        let gain = fa.value(per_game) - droppable[i].value(per_game);
+        if gain > 0.0 {
+            swaps.push(Swap {
+                add: fa.clone(),
+                drop: droppable.remove(i),
+                gain,
+            });
+        }
+    }
+
+    swaps
+}
+
+fn print_free_agents(free_agents: &[Candidate], f: &dyn Formatter) {
+    let columns = [
+        Column::new("Player", 20).truncated(),
+        Column::new("Pos", 4),
+        Column::new("G", 5).right(),
+        Column::new("FanPts", 8).right(),
+        Column::new("Pts/G", 8).right(),
+    ];
+    let rows: Vec<Vec<String>> = free_agents
+        .iter()
+        .map(|c| {
+            vec![
+                c.name.clone(),
+                c.primary_position.clone(),
+                c.games.to_string(),
+                format!("{:.2}", c.points),
+                format!("{:.2}", c.per_game()),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+fn print_swaps(swaps: &[Swap], f: &dyn Formatter) {
+    if swaps.is_empty() {
+        println!("No free agent outperforms the rostered players.");
+        return;
+    }
+
+    let columns = [
+        Column::new("Add", 20).truncated(),
+        Column::new("AddPos", 7),
+        Column::new("AddPts", 8).right(),
+        Column::new("AddPts/G", 10).right(),
+        Column::new("Drop", 20).truncated(),
+        Column::new("DropPos", 8),
+        Column::new("DropPts", 8).right(),
+        Column::new("DropPts/G", 10).right(),
+        Column::new("Gain", 8).right().points(),
+    ];
+    let rows: Vec<Vec<String>> = swaps
+        .iter()
+        .map(|sw| {
+            vec![
+                sw.add.name.clone(),
+                sw.add.primary_position.clone(),
+                format!("{:.2}", sw.add.points),
+                format!("{:.2}", sw.add.per_game()),
+                sw.drop.name.clone(),
+                sw.drop.primary_position.clone(),
+                format!("{:.2}", sw.drop.points),
+                format!("{:.2}", sw.drop.per_game()),
+                format!("{:.2}", sw.gain),
+            ]
+        })
+        .collect();
+    println!("{}", f.table(&columns, &rows));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn candidate(name: &str, team: &str, pos: &str, games: usize, points: f32) -> Candidate {
+        Candidate {
+            name: name.to_string(),
+            team: team.to_string(),
+            pitcher: pos == "SP" || pos == "RP",
+            primary_position: pos.to_string(),
+            games,
+            points,
+        }
+    }
+
+    #[test]
+    fn suggest_swaps_should_replace_weakest_player_of_same_position() {
+        let free_agents = vec![
+            candidate("FA SS", "<FA>", "SS", 5, 30.0),
+            candidate("FA SP", "<FA>", "SP", 2, 25.0),
+            candidate("FA C", "<FA>", "C", 5, 12.0),
+            candidate("FA RP", "<FA>", "RP", 4, 3.0),
+        ];
+        let rostered = vec![
+            candidate("My SS", "A", "SS", 5, 20.0),
+            candidate("My 1B", "A", "1B", 5, 5.0),
+            candidate("My SP", "A", "SP", 1, 15.0),
+            candidate("My RP", "A", "RP", 3, 6.0),
+        ];
+
+        let swaps = suggest_swaps(&free_agents, &rostered, false, 10);
+        let pairs: Vec<_> = swaps
+            .iter()
+            .map(|sw| (sw.add.name.as_str(), sw.drop.name.as_str()))
+            .collect();
+        assert_eq!(
+            vec![("FA SS", "My SS"), ("FA SP", "My SP"), ("FA C", "My 1B")],
+            pairs
+        );
+        assert!((swaps[0].gain - 10.0).abs() < 0.0001);
+
+        let swaps = suggest_swaps(&free_agents, &rostered, true, 10);
+        let pairs: Vec<_> = swaps
+            .iter()
+            .map(|sw| (sw.add.name.as_str(), sw.drop.name.as_str()))
+            .collect();
+        // FA SP (12.5/G) does not beat My SP (15.0/G) by points per game
+        assert_eq!(vec![("FA SS", "My SS"), ("FA C", "My 1B")], pairs);
+    }
+
+    #[test]
+    fn suggest_swaps_should_not_drop_players_without_games() {
+        let free_agents = vec![candidate("FA C", "<FA>", "C", 5, 12.0)];
+        let rostered = vec![
+            candidate("My Injured OF", "A", "", 0, 0.0),
+            candidate("My C", "A", "C", 5, 10.0),
+        ];
+
+        let swaps = suggest_swaps(&free_agents, &rostered, false, 10);
+        assert_eq!(1, swaps.len());
+        assert_eq!("My C", swaps[0].drop.name);
+    }
+}
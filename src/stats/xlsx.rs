@@ -0,0 +1,205 @@
+use crate::league::{roster, scoring};
+use crate::stats::{self, output, FantasyPlayer};
+
+use clap::ArgMatches;
+use rust_xlsxwriter::{Format, Workbook};
+use std::error::Error;
+use unicode_width::UnicodeWidthStr;
+
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Columns written as text even if they look like numbers. Innings pitched are in thirds,
+/// e.g. `6.2` for 6 2/3 innings, so they are not decimals.
+const TEXT_COLUMNS: [&str; 1] = ["P.IP"];
+
+pub fn show(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let env_api_key = stats::get_env_api_key();
+    let config = stats::get_config(matches, &env_api_key)?;
+    let top_n = matches.value_of("top").unwrap().parse::<usize>()?;
+    let path = matches.value_of("output").unwrap();
+
+    let league = config.league.to_string();
+    let s = scoring::load(&league)?;
+    let r = roster::load(&league)?;
+
+    let data = stats::report_data(&config, &s, &r, top_n)?;
+    let mut weekly_headers = vec!["Date".to_string()];
+    weekly_headers.extend(data.weekly_changes.1.iter().map(|(t, _)| t.clone()));
+    let (mut weekly_rows, total) =
+        stats::weekly_change_rows(&data.weekly_changes, |p| format!("{:.2}", p));
+    weekly_rows.push(total);
+
+    let sheets = vec![
+        player_sheet("Players", &data.players, s.get_header_items()),
+        player_sheet(
+            "Top Batters",
+            &data.top_batters,
+            s.get_header_items_for_batter(),
+        ),
+        player_sheet(
+            "Top Pitchers",
+            &data.top_pitchers,
+            s.get_header_items_for_pitcher(),
+        ),
+        Sheet {
+            name: "Team Rankings",
+            headers: vec!["Rank".to_string(), "Team".to_string(), "FanPts".to_string()],
+            rows: data
+                .scores
+                .iter()
+                .enumerate()
+                .map(|(i, (team, pts))| {
+                    vec![(i + 1).to_string(), team.clone(), format!("{:.2}", pts)]
+                })
+                .collect(),
+        },
+        Sheet {
+            name: "Weekly Changes",
+            headers: weekly_headers,
+            rows: weekly_rows,
+        },
+    ];
+
+    workbook(&sheets)?.save(path)?;
+    println!("workbook written to {}", path);
+
+    Ok(())
+}
+
+/// A worksheet with a header row. Cells which are numbers are written as numbers.
+struct Sheet {
+    name: &'static str,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+fn player_sheet(name: &'static str, players: &[FantasyPlayer], headers: Vec<String>) -> Sheet {
+    Sheet {
+        name,
+        rows: players
+            .iter()
+            .map(|p| output::get_stat_cells(p, &headers))
+            .collect(),
+        headers,
+    }
+}
+
+/// Returns the workbook of the sheets, with the header rows bold and frozen and the columns
+/// sized to the cells.
+fn workbook(sheets: &[Sheet]) -> Result<Workbook, Box<dyn Error>> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+
+    for sheet in sheets.iter() {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet.name)?;
+        worksheet.set_freeze_panes(1, 0)?;
+
+        for (c, header) in sheet.headers.iter().enumerate() {
+            let c = c as u16;
+            worksheet.write_string_with_format(0, c, header, &bold)?;
+            worksheet.set_column_width(c, column_width(sheet, c as usize) as f64)?;
+        }
+        for (r, cells) in sheet.rows.iter().enumerate() {
+            let r = r as u32 + 1;
+            for (c, cell) in cells.iter().enumerate() {
+                let text = sheet
+                    .headers
+                    .get(c)
+                    .is_some_and(|h| TEXT_COLUMNS.contains(&h.as_str()));
+                match cell.parse::<f64>() {
+                    Ok(n) if !text && n.is_finite() => {
+                        worksheet.write_number(r, c as u16, n)?;
+                    }
+                    _ => {
+                        worksheet.write_string(r, c as u16, cell)?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(workbook)
+}
+
+/// Returns the width of the column by the display width of the header and the cells.
+fn column_width(sheet: &Sheet, i: usize) -> usize {
+    sheet
+        .rows
+        .iter()
+        .filter_map(|r| r.get(i))
+        .map(|c| UnicodeWidthStr::width(c.as_str()))
+        .fold(
+            UnicodeWidthStr::width(sheet.headers[i].as_str()),
+            usize::max,
+        )
+        .min(MAX_COLUMN_WIDTH)
+        + 2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    fn part(xlsx: &[u8], name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(Cursor::new(xlsx)).unwrap();
+        let mut xml = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    #[test]
+    fn workbook_should_type_numbers_but_innings_and_freeze_header() {
+        let sheets = [
+            Sheet {
+                name: "Players",
+                headers: vec![
+                    "Player".to_string(),
+                    "FanPts".to_string(),
+                    "P.IP".to_string(),
+                ],
+                rows: vec![vec![
+                    "José Berríos".to_string(),
+                    "21.50".to_string(),
+                    "6.2".to_string(),
+                ]],
+            },
+            Sheet {
+                name: "Team Rankings",
+                headers: vec!["Team".to_string()],
+                rows: vec![],
+            },
+        ];
+        let xlsx = workbook(&sheets).unwrap().save_to_buffer().unwrap();
+
+        let workbook = part(&xlsx, "xl/workbook.xml");
+        assert!(workbook.contains("name=\"Players\""));
+        assert!(workbook.contains("name=\"Team Rankings\""));
+
+        let sheet = part(&xlsx, "xl/worksheets/sheet1.xml");
+        assert!(sheet.contains("state=\"frozen\""));
+        assert!(sheet.contains("<c r=\"B2\"><v>21.5</v></c>"));
+        assert!(!sheet.contains("<c r=\"C2\"><v>"));
+        assert!(sheet.contains("<c r=\"C2\" t=\"s\">"));
+
+        let strings = part(&xlsx, "xl/sharedStrings.xml");
+        assert!(strings.contains("José Berríos"));
+        assert!(strings.contains("6.2"));
+    }
+
+    #[test]
+    fn column_width_should_fit_cells_up_to_max() {
+        let sheet = Sheet {
+            name: "Players",
+            headers: vec!["Player".to_string(), "FanPts".to_string()],
+            rows: vec![vec!["가".repeat(30), "1.00".to_string()]],
+        };
+        assert_eq!(MAX_COLUMN_WIDTH + 2, column_width(&sheet, 0));
+        assert_eq!(8, column_width(&sheet, 1));
+    }
+}
@@ -1,7 +1,16 @@
 use chrono::prelude::*;
+use chrono::Duration;
 use std::env;
 use std::error::Error;
-use time::Duration;
+
+const NO_GAME_DATES: [&str; 6] = [
+    "2019-03-25",
+    "2019-03-26",
+    "2019-03-27",
+    "2019-07-08",
+    "2019-07-09",
+    "2019-07-10",
+];
 
 pub fn get_json_res(url: &str) -> Result<String, Box<dyn Error>> {
     Ok(reqwest::get(url)?.text()?)
@@ -28,13 +37,13 @@ pub fn date_strs(date: &str, range: &str) -> Vec<String> {
     }
 
     if range == "all" {
-        let mut dt = Utc.ymd(2019, 3, 28).and_hms(0, 0, 0);
+        let mut dt = Utc.with_ymd_and_hms(2019, 3, 28, 0, 0, 0).unwrap();
         while dt < last_dt {
             let date_str = dt.format("%Y-%m-%d").to_string();
             if !asg_dates.iter().any(|&d| d == &date_str[..]) {
                 result.push(date_str);
             }
-            dt = dt + Duration::days(1);
+            dt += Duration::days(1);
         }
         result.sort();
         return result;
@@ -52,7 +61,7 @@ pub fn date_strs(date: &str, range: &str) -> Vec<String> {
         if !asg_dates.iter().any(|&d| d == &date_str[..]) {
             result.push(date_str);
         }
-        dt = dt - Duration::days(1);
+        dt -= Duration::days(1);
     }
 
     result
@@ -83,35 +92,71 @@ pub fn weekly_date_strs(date: &str) -> Vec<String> {
         Weekday::Sun => dt - Duration::days(6),
     };
 
-    if dt < Utc.ymd(2019, 3, 25).and_hms(0, 0, 0) {
+    if dt < Utc.with_ymd_and_hms(2019, 3, 25, 0, 0, 0).unwrap() {
         return vec![];
     }
 
     if dt.format("%Y-%m-%d").to_string() == "2019-07-15" {
-        dt = dt - Duration::days(7);
+        dt -= Duration::days(7);
     }
 
-    let no_game_dates = vec![
-        "2019-03-25",
-        "2019-03-26",
-        "2019-03-27",
-        "2019-07-08",
-        "2019-07-09",
-        "2019-07-10",
-    ];
-
     let mut result = vec![];
     let mut s = dt.format("%Y-%m-%d").to_string();
     while s != expired_dt.format("%Y-%m-%d").to_string() {
-        if !no_game_dates.iter().any(|&x| x == s) {
+        if !NO_GAME_DATES.iter().any(|&x| x == s) {
             result.push(s);
         }
-        dt = dt + Duration::days(1);
+        dt += Duration::days(1);
         s = dt.format("%Y-%m-%d").to_string();
     }
     return result;
 }
 
+pub fn remaining_week_date_strs(date: &str) -> Vec<String> {
+    let dt = format!("{}T00:00:00Z", date)
+        .parse::<DateTime<Utc>>()
+        .expect("error parsing date string");
+
+    let mut last_dt = dt + Duration::days(6 - i64::from(dt.weekday().num_days_from_monday()));
+    if (last_dt - Duration::days(6)).format("%Y-%m-%d").to_string() == "2019-07-08" {
+        last_dt += Duration::days(7);
+    }
+
+    let mut result = vec![];
+    let mut dt = dt + Duration::days(1);
+    while dt <= last_dt {
+        let s = dt.format("%Y-%m-%d").to_string();
+        if !NO_GAME_DATES.iter().any(|&x| x == s) {
+            result.push(s);
+        }
+        dt += Duration::days(1);
+    }
+    result
+}
+
+pub fn season_week_start_strs() -> Vec<String> {
+    let mut dt = Utc.with_ymd_and_hms(2019, 3, 25, 0, 0, 0).unwrap();
+    let last_dt = Utc.with_ymd_and_hms(2019, 9, 23, 0, 0, 0).unwrap();
+
+    let mut result = vec![];
+    while dt <= last_dt {
+        let s = dt.format("%Y-%m-%d").to_string();
+        // week of 2019-07-15 is merged into the all-star week (see `weekly_date_strs`)
+        if s != "2019-07-15" {
+            result.push(s);
+        }
+        dt += Duration::days(7);
+    }
+    result
+}
+
+pub fn add_days(date: &str, days: i64) -> String {
+    let dt = format!("{}T00:00:00Z", date)
+        .parse::<DateTime<Utc>>()
+        .expect("error parsing date string");
+    (dt + Duration::days(days)).format("%Y-%m-%d").to_string()
+}
+
 #[allow(dead_code)]
 pub fn assert_eq_f32(a: f32, b: f32) {
     let a = (a * 100.0) as i32;
@@ -225,4 +270,38 @@ mod test {
             weekly_date_strs("2019-07-21")
         );
     }
+
+    #[test]
+    fn remaining_week_date_strs_should_return_dates_until_the_end_of_the_week() {
+        assert_eq!(
+            vec!["2019-06-15".to_string(), "2019-06-16".to_string()],
+            remaining_week_date_strs("2019-06-14")
+        );
+        let empty: Vec<String> = vec![];
+        assert_eq!(empty, remaining_week_date_strs("2019-06-16"));
+        assert_eq!(empty, remaining_week_date_strs("2019-07-07"));
+        assert_eq!(
+            vec!["2019-07-11".to_string(), "2019-07-12".to_string()],
+            remaining_week_date_strs("2019-07-08")[..2].to_vec()
+        );
+        assert_eq!(
+            "2019-07-21",
+            remaining_week_date_strs("2019-07-16").last().unwrap()
+        );
+    }
+
+    #[test]
+    fn season_week_start_strs_should_return_mondays_without_merged_week() {
+        let starts = season_week_start_strs();
+        assert_eq!(26, starts.len());
+        assert_eq!("2019-03-25", starts.first().unwrap());
+        assert_eq!("2019-09-23", starts.last().unwrap());
+        assert_eq!(false, starts.iter().any(|s| s == "2019-07-15"));
+    }
+
+    #[test]
+    fn add_days_should_return_shifted_date_string() {
+        assert_eq!("2019-04-01", add_days("2019-03-31", 1));
+        assert_eq!("2019-07-14", add_days("2019-07-21", -7));
+    }
 }